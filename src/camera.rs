@@ -0,0 +1,258 @@
+use crate::{
+    canvas::Canvas, matrix::Matrix4, point::Point, ray::Ray, transform::translation,
+    vector::Vector, world::World,
+};
+
+/// Orients the world relative to an eye at `from` looking toward `to`.
+pub(crate) fn view_transform(from: Point, to: Point, up: Vector) -> Matrix4 {
+    let forward = (to - from).normalize();
+    let left = forward * up.normalize();
+    let true_up = left * forward;
+    let orientation = Matrix4::new([
+        [left.x, left.y, left.z, 0.0],
+        [true_up.x, true_up.y, true_up.z, 0.0],
+        [-forward.x, -forward.y, -forward.z, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+    orientation * translation(-from.x, -from.y, -from.z)
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Camera {
+    hsize: usize,
+    vsize: usize,
+    field_of_view: f32,
+    transform: Matrix4,
+    inverse: Matrix4,
+    half_width: f32,
+    half_height: f32,
+    pixel_size: f32,
+}
+
+impl Camera {
+    pub fn new(hsize: usize, vsize: usize, field_of_view: f32) -> Self {
+        // The canvas sits one unit in front of the eye
+        let half_view = (field_of_view / 2.0).tan();
+        let aspect = hsize as f32 / vsize as f32;
+        let (half_width, half_height) = if aspect >= 1.0 {
+            (half_view, half_view / aspect)
+        } else {
+            (half_view * aspect, half_view)
+        };
+        Camera {
+            hsize,
+            vsize,
+            field_of_view,
+            transform: Matrix4::identity(),
+            inverse: Matrix4::identity(),
+            half_width,
+            half_height,
+            pixel_size: half_width * 2.0 / hsize as f32,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn hsize(&self) -> usize {
+        self.hsize
+    }
+
+    #[allow(dead_code)]
+    pub fn vsize(&self) -> usize {
+        self.vsize
+    }
+
+    #[allow(dead_code)]
+    pub fn field_of_view(&self) -> f32 {
+        self.field_of_view
+    }
+
+    #[allow(dead_code)]
+    pub fn pixel_size(&self) -> f32 {
+        self.pixel_size
+    }
+
+    #[allow(dead_code)]
+    pub fn transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    pub fn set_transform(&mut self, transform: Matrix4) {
+        self.inverse = transform
+            .inverse()
+            .expect("camera transform must be invertible");
+        self.transform = transform;
+    }
+
+    pub fn with_transform(mut self, transform: Matrix4) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    /// A ray from the eye through the center of pixel (`x`, `y`).
+    pub fn ray_for_pixel(&self, x: usize, y: usize) -> Ray {
+        let x_offset = (x as f32 + 0.5) * self.pixel_size;
+        let y_offset = (y as f32 + 0.5) * self.pixel_size;
+        // The camera looks toward -z, so +x is to the left
+        let world_x = self.half_width - x_offset;
+        let world_y = self.half_height - y_offset;
+
+        let pixel = self.inverse * Point::new(world_x, world_y, -1.0);
+        let origin = self.inverse * Point::origin();
+        Ray::new(origin, (pixel - origin).normalize())
+    }
+
+    pub fn render(&self, world: &World) -> Canvas {
+        let mut image = Canvas::new(self.hsize, self.vsize);
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                let ray = self.ray_for_pixel(x, y);
+                image.write_pixel(x, y, world.color_at(&ray));
+            }
+        }
+        image
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{FRAC_1_SQRT_2, PI};
+
+    use float_cmp::approx_eq;
+
+    use crate::{
+        camera::{view_transform, Camera},
+        color::Color,
+        matrix::Matrix4,
+        point::Point,
+        transform::{rotation_y, scaling, translation},
+        vector::Vector,
+        world::World,
+    };
+
+    #[test]
+    fn default_orientation_view_transform() {
+        let transform = view_transform(
+            Point::origin(),
+            Point::new(0.0, 0.0, -1.0),
+            Vector::y_unit(),
+        );
+        assert_eq!(transform, Matrix4::identity());
+    }
+    #[test]
+    fn view_transform_looking_in_positive_z() {
+        let transform =
+            view_transform(Point::origin(), Point::new(0.0, 0.0, 1.0), Vector::y_unit());
+        assert_eq!(transform, scaling(-1.0, 1.0, -1.0));
+    }
+    #[test]
+    fn view_transform_moves_world() {
+        let transform =
+            view_transform(Point::new(0.0, 0.0, 8.0), Point::origin(), Vector::y_unit());
+        assert_eq!(transform, translation(0.0, 0.0, -8.0));
+    }
+    #[test]
+    fn arbitrary_view_transform() {
+        let transform = view_transform(
+            Point::new(1.0, 3.0, 2.0),
+            Point::new(4.0, -2.0, 8.0),
+            Vector::new(1.0, 1.0, 0.0),
+        );
+        assert!(approx_eq!(
+            Matrix4,
+            transform,
+            Matrix4::new([
+                [-0.50709, 0.50709, 0.67612, -2.36643],
+                [0.76772, 0.60609, 0.12122, -2.82843],
+                [-0.35857, 0.59761, -0.71714, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ]),
+            epsilon = 0.00001
+        ));
+    }
+    #[test]
+    fn construct_camera() {
+        let camera = Camera::new(160, 120, PI / 2.0);
+        assert_eq!(camera.hsize(), 160);
+        assert_eq!(camera.vsize(), 120);
+        assert_eq!(camera.field_of_view(), PI / 2.0);
+        assert_eq!(camera.transform(), Matrix4::identity());
+    }
+    #[test]
+    fn pixel_size_for_horizontal_canvas() {
+        let camera = Camera::new(200, 125, PI / 2.0);
+        assert!(approx_eq!(
+            f32,
+            camera.pixel_size(),
+            0.01,
+            epsilon = 0.00001
+        ));
+    }
+    #[test]
+    fn pixel_size_for_vertical_canvas() {
+        let camera = Camera::new(125, 200, PI / 2.0);
+        assert!(approx_eq!(
+            f32,
+            camera.pixel_size(),
+            0.01,
+            epsilon = 0.00001
+        ));
+    }
+    #[test]
+    fn ray_through_center_of_canvas() {
+        let camera = Camera::new(201, 101, PI / 2.0);
+        let ray = camera.ray_for_pixel(100, 50);
+        assert_eq!(ray.origin, Point::origin());
+        assert!(approx_eq!(
+            Vector,
+            ray.direction,
+            Vector::new(0.0, 0.0, -1.0),
+            epsilon = 0.00001
+        ));
+    }
+    #[test]
+    fn ray_through_corner_of_canvas() {
+        let camera = Camera::new(201, 101, PI / 2.0);
+        let ray = camera.ray_for_pixel(0, 0);
+        assert_eq!(ray.origin, Point::origin());
+        assert!(approx_eq!(
+            Vector,
+            ray.direction,
+            Vector::new(0.66519, 0.33259, -0.66851),
+            epsilon = 0.00001
+        ));
+    }
+    #[test]
+    fn ray_when_camera_is_transformed() {
+        let camera = Camera::new(201, 101, PI / 2.0)
+            .with_transform(rotation_y(PI / 4.0) * translation(0.0, -2.0, 5.0));
+        let ray = camera.ray_for_pixel(100, 50);
+        assert!(approx_eq!(
+            Point,
+            ray.origin,
+            Point::new(0.0, 2.0, -5.0),
+            epsilon = 0.00001
+        ));
+        assert!(approx_eq!(
+            Vector,
+            ray.direction,
+            Vector::new(FRAC_1_SQRT_2, 0.0, -FRAC_1_SQRT_2),
+            epsilon = 0.00001
+        ));
+    }
+    #[test]
+    fn render_world_with_camera() {
+        let world = World::test_default();
+        let camera = Camera::new(11, 11, PI / 2.0).with_transform(view_transform(
+            Point::new(0.0, 0.0, -5.0),
+            Point::origin(),
+            Vector::y_unit(),
+        ));
+        let image = camera.render(&world);
+        assert!(approx_eq!(
+            Color,
+            image.pixel_at(5, 5),
+            Color::new(0.38066, 0.47583, 0.2855),
+            epsilon = 0.0001
+        ));
+    }
+}
@@ -1,12 +1,18 @@
-use crate::color::*;
+use crate::{color::*, tone_map::ToneMap};
 use std::fs::File;
-use std::io::prelude::*;
+use std::io::{prelude::*, BufWriter};
+use std::path::Path;
+
+/// Plain PPM lines should not exceed this many characters.
+const PPM_LINE_WIDTH: usize = 70;
 
 #[derive(Debug)]
 pub(crate) struct Canvas {
     width: usize,
     height: usize,
     pixels: Vec<Color>,
+    /// How colors are brought into range when writing 8-bit images
+    tone_map: ToneMap,
 }
 
 impl Canvas {
@@ -15,40 +21,122 @@ impl Canvas {
             width,
             height,
             pixels: vec![BLACK; width * height],
+            tone_map: ToneMap::default(),
         }
     }
 
+    #[allow(dead_code)]
+    pub fn tone_map(&self) -> ToneMap {
+        self.tone_map
+    }
+
+    pub fn set_tone_map(&mut self, tone_map: ToneMap) {
+        self.tone_map = tone_map;
+    }
+
+    pub fn with_tone_map(mut self, tone_map: ToneMap) -> Self {
+        self.set_tone_map(tone_map);
+        self
+    }
+
     pub fn write_pixel(&mut self, x: usize, y: usize, color: Color) {
         if x < self.width && y < self.height {
             self.pixels[x + y * self.width] = color;
         }
     }
 
+    #[allow(dead_code)]
     pub fn pixel_at(&self, x: usize, y: usize) -> Color {
         match self.pixels.get(x + y * self.width) {
-            Some(color) => color.clone(),
+            Some(color) => *color,
             None => BLACK,
         }
     }
 
-    pub fn to_ppm(self) -> std::io::Result<()> {
-        let mut file = File::create("foo.ppm")?;
-        file.write_fmt(format_args!("P3\n{} {}\n255\n", self.width, self.height))?;
-        file.write(
-            self.pixels
-                .iter()
-                .map(|color| color.to_string())
-                .collect::<String>()
-                .as_bytes(),
-        )
-        .unwrap();
-        Ok(())
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Pixels as written to images, in row-major order: canvas colors are
+    /// linear light, so they're tone-mapped, then sRGB-encoded, then
+    /// quantized.
+    pub fn rgb8_pixels(&self) -> impl Iterator<Item = [u8; 3]> + '_ {
+        self.pixels
+            .iter()
+            .map(|color| self.tone_map.apply(*color).encode_srgb().to_rgb8())
+    }
+
+    /// Writes the canvas as a plain (P3) PPM. Each row starts on a new line,
+    /// and lines are wrapped so none exceeds 70 characters.
+    pub fn write_ppm<W: Write>(&self, mut w: W) -> std::io::Result<()> {
+        write!(w, "P3\n{} {}\n255\n", self.width, self.height)?;
+        let pixels: Vec<[u8; 3]> = self.rgb8_pixels().collect();
+        for row in pixels.chunks(self.width.max(1)) {
+            let mut line = String::new();
+            for value in row.iter().flatten() {
+                let value = value.to_string();
+                if !line.is_empty() && line.len() + 1 + value.len() > PPM_LINE_WIDTH {
+                    writeln!(w, "{line}")?;
+                    line.clear();
+                }
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(&value);
+            }
+            writeln!(w, "{line}")?;
+        }
+        w.flush()
+    }
+
+    /// Writes the canvas as a binary (P6) PPM, one byte per channel.
+    pub fn write_binary_ppm<W: Write>(&self, mut w: W) -> std::io::Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+        let raster: Vec<u8> = self.rgb8_pixels().flatten().collect();
+        w.write_all(&raster)?;
+        w.flush()
+    }
+
+    /// Saves to `path`, choosing the format from its extension: `.png`, or
+    /// binary PPM for `.ppm`. Plain PPM has no extension of its own; use
+    /// `write_ppm` or `to_ppm_string` for it.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        let write = match extension.as_deref() {
+            Some("png") => Canvas::write_png,
+            Some("ppm") => Canvas::write_binary_ppm,
+            _ => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("unsupported image format: {}", path.display()),
+                ))
+            }
+        };
+        write(self, BufWriter::new(File::create(path)?))
+    }
+
+    /// The plain (P3) PPM as a string, for in-memory pipelines and tests.
+    #[allow(dead_code)]
+    pub fn to_ppm_string(&self) -> String {
+        let mut buffer = vec![];
+        self.write_ppm(&mut buffer)
+            .expect("writing to a Vec cannot fail");
+        String::from_utf8(buffer).expect("PPM output is ASCII")
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{canvas::Canvas, color::*};
+    use crate::{canvas::Canvas, color::*, tone_map::ToneMap};
+    use float_cmp::approx_eq;
 
     #[test]
     fn new_canvas_is_black() {
@@ -62,4 +150,129 @@ mod tests {
         canvas.write_pixel(1, 1, WHITE);
         assert_eq!(canvas.pixel_at(1, 1), WHITE);
     }
+    #[test]
+    fn ppm_header() {
+        let canvas = Canvas::new(5, 3);
+        let ppm = canvas.to_ppm_string();
+        let lines: Vec<&str> = ppm.lines().take(3).collect();
+        assert_eq!(lines, vec!["P3", "5 3", "255"]);
+    }
+    #[test]
+    fn ppm_pixel_data() {
+        let mut canvas = Canvas::new(5, 3);
+        canvas.write_pixel(0, 0, Color::new(1.5, 0.0, 0.0));
+        canvas.write_pixel(2, 1, Color::new(0.0, 1.0, 0.0));
+        canvas.write_pixel(4, 2, Color::new(-0.5, 0.0, 1.0));
+        let ppm = canvas.to_ppm_string();
+        let lines: Vec<&str> = ppm.lines().skip(3).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "255 0 0 0 0 0 0 0 0 0 0 0 0 0 0",
+                "0 0 0 0 0 0 0 255 0 0 0 0 0 0 0",
+                "0 0 0 0 0 0 0 0 0 0 0 0 0 0 255",
+            ]
+        );
+    }
+    #[test]
+    fn ppm_splits_long_lines() {
+        let mut canvas = Canvas::new(10, 2);
+        for y in 0..2 {
+            for x in 0..10 {
+                canvas.write_pixel(x, y, Color::new(1.0, 0.0, 1.0));
+            }
+        }
+        let ppm = canvas.to_ppm_string();
+        let lines: Vec<&str> = ppm.lines().skip(3).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "255 0 255 255 0 255 255 0 255 255 0 255 255 0 255 255 0 255 255 0 255",
+                "255 0 255 255 0 255 255 0 255",
+                "255 0 255 255 0 255 255 0 255 255 0 255 255 0 255 255 0 255 255 0 255",
+                "255 0 255 255 0 255 255 0 255",
+            ]
+        );
+        assert!(lines.iter().all(|line| line.len() <= 70));
+    }
+    #[test]
+    fn ppm_ends_with_newline() {
+        let canvas = Canvas::new(5, 3);
+        assert!(canvas.to_ppm_string().ends_with('\n'));
+    }
+    #[test]
+    fn write_ppm_to_any_writer() {
+        let canvas = Canvas::new(1, 1);
+        let mut buffer = vec![];
+        canvas.write_ppm(&mut buffer).unwrap();
+        assert_eq!(buffer, b"P3\n1 1\n255\n0 0 0\n");
+    }
+    #[test]
+    fn binary_ppm() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.write_pixel(0, 0, Color::new(1.0, 0.0, 1.0));
+        let mut buffer = vec![];
+        canvas.write_binary_ppm(&mut buffer).unwrap();
+        assert_eq!(buffer, b"P6\n2 1\n255\n\xff\x00\xff\x00\x00\x00");
+    }
+    #[test]
+    fn save_by_extension() {
+        let mut canvas = Canvas::new(3, 2);
+        canvas.write_pixel(2, 1, WHITE);
+        let grey = Color::new(0.5, 0.5, 0.5);
+        canvas.write_pixel(1, 0, grey);
+        let dir = std::env::temp_dir();
+
+        let ppm = dir.join(format!("canvas-save-{}.PPM", std::process::id()));
+        canvas.save(&ppm).unwrap();
+        let read = Canvas::read_ppm(std::fs::read(&ppm).unwrap().as_slice()).unwrap();
+        std::fs::remove_file(&ppm).unwrap();
+        assert_eq!(read.pixel_at(2, 1), WHITE);
+        // Mid-grey survives the sRGB encode and decode up to 8-bit quantization
+        assert!(approx_eq!(
+            Color,
+            read.pixel_at(1, 0),
+            grey,
+            epsilon = 0.005
+        ));
+
+        let png = dir.join(format!("canvas-save-{}.png", std::process::id()));
+        canvas.save(&png).unwrap();
+        let bytes = std::fs::read(&png).unwrap();
+        std::fs::remove_file(&png).unwrap();
+        assert_eq!(&bytes[..8], b"\x89PNG\r\n\x1a\n");
+    }
+    #[test]
+    fn save_rejects_unknown_extension() {
+        let canvas = Canvas::new(1, 1);
+        let path = std::env::temp_dir().join("canvas-save.bmp");
+        let err = canvas.save(&path).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(!path.exists());
+    }
+    #[test]
+    fn ppm_quantizes_fractional_channels() {
+        let mut canvas = Canvas::new(1, 2);
+        canvas.write_pixel(0, 0, Color::new(0.0, 0.5, 0.0));
+        canvas.write_pixel(0, 1, Color::new(1.0, 0.8, 0.6));
+        let ppm = canvas.to_ppm_string();
+        let lines: Vec<&str> = ppm.lines().skip(3).collect();
+        assert_eq!(lines, vec!["0 188 0", "255 231 203"]);
+    }
+    #[test]
+    fn writers_apply_tone_map() {
+        let mut canvas = Canvas::new(1, 1).with_tone_map(ToneMap::Reinhard);
+        canvas.write_pixel(0, 0, Color::new(1.0, 3.0, 0.0));
+        assert_eq!(canvas.tone_map(), ToneMap::Reinhard);
+        assert_eq!(
+            canvas.rgb8_pixels().collect::<Vec<_>>(),
+            vec![[188, 225, 0]]
+        );
+        assert!(canvas.to_ppm_string().ends_with("188 225 0\n"));
+        let mut binary = vec![];
+        canvas.write_binary_ppm(&mut binary).unwrap();
+        assert!(binary.ends_with(&[188, 225, 0]));
+        // The stored color keeps its full range
+        assert_eq!(canvas.pixel_at(0, 0), Color::new(1.0, 3.0, 0.0));
+    }
 }
@@ -1,4 +1,4 @@
-use float_cmp::approx_eq;
+use float_cmp::{approx_eq, ApproxEq, F32Margin};
 use std::fmt;
 
 pub(crate) const BLACK: Color = Color {
@@ -13,7 +13,7 @@ pub(crate) const WHITE: Color = Color {
     b: 1.0,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Copy, Clone, Debug)]
 pub(crate) struct Color {
     pub r: f32,
     pub g: f32,
@@ -24,13 +24,47 @@ impl Color {
     pub fn new(r: f32, g: f32, b: f32) -> Self {
         Color { r, g, b }
     }
+
+    /// Channels clamped and quantized to 0..=255.
+    pub fn to_rgb8(self) -> [u8; 3] {
+        // Scale before rounding, or every channel snaps to 0 or 255
+        let quantize = |channel: f32| (channel.clamp(0.0, 1.0) * 255.0).round() as u8;
+        [quantize(self.r), quantize(self.g), quantize(self.b)]
+    }
+
+    /// Encodes linear light with the sRGB transfer function, as image files
+    /// and displays expect.
+    pub fn encode_srgb(self) -> Self {
+        self.map(|c| {
+            if c <= 0.003_130_8 {
+                12.92 * c
+            } else {
+                1.055 * c.powf(1.0 / 2.4) - 0.055
+            }
+        })
+    }
+
+    /// Inverse of `encode_srgb`: converts sRGB-encoded values back to linear light.
+    pub fn decode_srgb(self) -> Self {
+        self.map(|c| {
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        })
+    }
+
+    /// Applies `f` to each channel.
+    pub fn map(self, f: impl Fn(f32) -> f32) -> Self {
+        Color::new(f(self.r), f(self.g), f(self.b))
+    }
 }
 
 impl fmt::Display for Color {
+    #[allow(clippy::write_with_newline)]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let r = (self.r.clamp(0.0, 1.0).round() * 255.0) as u8;
-        let g = (self.g.clamp(0.0, 1.0).round() * 255.0) as u8;
-        let b = (self.b.clamp(0.0, 1.0).round() * 255.0) as u8;
+        let [r, g, b] = self.to_rgb8();
         write!(f, "{} {} {}\n", r, g, b)
     }
 }
@@ -43,6 +77,17 @@ impl PartialEq for Color {
     }
 }
 
+impl ApproxEq for Color {
+    type Margin = F32Margin;
+
+    fn approx_eq<M: Into<Self::Margin>>(self, other: Self, margin: M) -> bool {
+        let margin = margin.into();
+        self.r.approx_eq(other.r, margin)
+            && self.g.approx_eq(other.g, margin)
+            && self.b.approx_eq(other.b, margin)
+    }
+}
+
 impl std::ops::Add<Color> for Color {
     type Output = Color;
 
@@ -85,9 +130,12 @@ impl std::ops::Div<f32> for Color {
 
 #[cfg(test)]
 mod tests {
-    use crate::color::Color;
+    use float_cmp::approx_eq;
+
+    use crate::color::{Color, BLACK, WHITE};
 
     #[test]
+    #[allow(clippy::excessive_precision)]
     fn two_colors_approx_eq() {
         let color1 = Color::new(0.0 + 0.5 - 0.3, 0.2, 1.0 / 1.5);
         let color2 = Color::new(0.2, 0.2, 0.66666667);
@@ -120,6 +168,42 @@ mod tests {
         assert_eq!(new_color, Color::new(-2.0, 1.0, 0.0));
     }
     #[test]
+    fn quantize_scales_before_rounding() {
+        assert_eq!(Color::new(0.5, 0.8, 0.6).to_rgb8(), [128, 204, 153]);
+        assert_eq!(Color::new(0.001, 0.999, 0.2).to_rgb8(), [0, 255, 51]);
+        assert_eq!(Color::new(-0.5, 1.5, 0.0).to_rgb8(), [0, 255, 0]);
+        assert_eq!(Color::new(0.0, 0.5, 1.0).to_string(), "0 128 255\n");
+    }
+    #[test]
+    fn srgb_encoding() {
+        assert_eq!(BLACK.encode_srgb(), BLACK);
+        assert_eq!(WHITE.encode_srgb(), WHITE);
+        let encoded = Color::new(0.5, 0.2140, 0.001).encode_srgb();
+        assert!(approx_eq!(
+            Color,
+            encoded,
+            Color::new(0.73536, 0.5, 0.01292),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn srgb_decoding_inverts_encoding() {
+        for c in [0.0, 0.002, 0.01, 0.18, 0.5, 0.9, 1.0] {
+            let color = Color::new(c, c, c);
+            assert!(approx_eq!(
+                Color,
+                color.encode_srgb().decode_srgb(),
+                color,
+                epsilon = 0.00001
+            ));
+        }
+    }
+    #[test]
+    fn map_each_channel() {
+        let color = Color::new(0.25, 1.0, 4.0).map(f32::sqrt);
+        assert_eq!(color, Color::new(0.5, 1.0, 2.0));
+    }
+    #[test]
     fn color_hadamard_product() {
         let hadamard_product = Color::new(0.5, 1.0, 0.0) * Color::new(0.25, 0.8, 3.0);
         assert_eq!(hadamard_product, Color::new(0.125, 0.8, 0.0));
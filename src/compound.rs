@@ -0,0 +1,57 @@
+//! Behavior shared by shapes built from other shapes, `Group` and `Csg`.
+//! A compound bakes its transform into its parts, so each part's
+//! `transform` is its full object-to-world transform through every
+//! enclosing compound, and parts convert points and normals between spaces
+//! on their own.
+
+use crate::{
+    intersection::Intersection, matrix::Matrix4, point::Point, ray::Ray, shape::Shape,
+    vector::Vector,
+};
+
+/// Swaps the compound's old transform, given by its inverse, out of each
+/// part for `transform`. Returns the inverse of `transform`.
+pub(crate) fn rebase<'a>(
+    parts: impl IntoIterator<Item = &'a mut Box<dyn Shape>>,
+    old_inverse: Matrix4,
+    transform: Matrix4,
+) -> Matrix4 {
+    let inverse = transform
+        .inverse()
+        .expect("compound transform must be invertible");
+    let rebase = transform * old_inverse;
+    for part in parts {
+        part.set_transform(rebase * part.transform());
+    }
+    inverse
+}
+
+/// Parts take world rays, so the local ray is sent back to world space for
+/// the compound's own `intersect`.
+pub(crate) fn local_intersect<'a>(compound: &'a dyn Shape, ray: &Ray) -> Vec<Intersection<'a>> {
+    compound
+        .intersect(&ray.transform(&compound.transform()))
+        .into_iter()
+        .collect()
+}
+
+/// A compound has no surface of its own: this is the normal of the part that
+/// was hit, expressed in the compound's space, or zero if `hit` isn't on one
+/// of its parts.
+pub(crate) fn local_normal_at(compound: &dyn Shape, point: Point, hit: &Intersection) -> Vector {
+    if !compound.includes(hit.object) {
+        return Vector::zero();
+    }
+    let transform = compound.transform();
+    let world_normal = hit.object.normal_at(transform * point, hit);
+    (transform.transpose() * world_normal).normalize()
+}
+
+/// Defers straight to the part that was hit, which already works in world
+/// space.
+pub(crate) fn normal_at(compound: &dyn Shape, world_point: Point, hit: &Intersection) -> Vector {
+    if !compound.includes(hit.object) {
+        return Vector::zero();
+    }
+    hit.object.normal_at(world_point, hit)
+}
@@ -0,0 +1,247 @@
+use crate::{
+    intersection::Intersection, material::Material, matrix::Matrix4, point::Point, ray::Ray,
+    shape::Shape, vector::Vector, EPSILON,
+};
+
+/// Double-napped cone around the y axis with its apex at the origin; the
+/// radius at any height equals `|y|`. Optionally truncated to
+/// `minimum..maximum` (exclusive) and capped at both ends when `closed`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Cone {
+    transform: Matrix4,
+    inverse: Matrix4,
+    material: Material,
+    pub minimum: f32,
+    pub maximum: f32,
+    pub closed: bool,
+}
+
+impl Cone {
+    pub fn new() -> Self {
+        Cone {
+            transform: Matrix4::identity(),
+            inverse: Matrix4::identity(),
+            material: Material::default(),
+            minimum: f32::NEG_INFINITY,
+            maximum: f32::INFINITY,
+            closed: false,
+        }
+    }
+
+    pub fn truncated(mut self, minimum: f32, maximum: f32, closed: bool) -> Self {
+        self.minimum = minimum;
+        self.maximum = maximum;
+        self.closed = closed;
+        self
+    }
+
+    fn intersect_caps<'a>(&'a self, ray: &Ray, intersections: &mut Vec<Intersection<'a>>) {
+        if !self.closed || ray.direction.y.abs() < EPSILON {
+            return;
+        }
+        // An unbounded end has no cap, however `closed` is set
+        for y in [self.minimum, self.maximum]
+            .into_iter()
+            .filter(|y| y.is_finite())
+        {
+            let t = (y - ray.origin.y) / ray.direction.y;
+            if within_radius(ray, t, y.abs()) {
+                intersections.push(Intersection::new(t, self));
+            }
+        }
+    }
+}
+
+impl Default for Cone {
+    fn default() -> Self {
+        Cone::new()
+    }
+}
+
+/// Whether the ray at `t` lies within `radius` of the y axis.
+fn within_radius(ray: &Ray, t: f32, radius: f32) -> bool {
+    let x = ray.origin.x + t * ray.direction.x;
+    let z = ray.origin.z + t * ray.direction.z;
+    x * x + z * z <= radius * radius + EPSILON
+}
+
+impl Shape for Cone {
+    fn transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn inverse(&self) -> Matrix4 {
+        self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix4) {
+        self.inverse = transform
+            .inverse()
+            .expect("cone transform must be invertible");
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let (origin, direction) = (ray.origin, ray.direction);
+        let a = direction.x * direction.x - direction.y * direction.y + direction.z * direction.z;
+        let b = 2.0 * origin.x * direction.x - 2.0 * origin.y * direction.y
+            + 2.0 * origin.z * direction.z;
+        let c = origin.x * origin.x - origin.y * origin.y + origin.z * origin.z;
+
+        let mut candidates = vec![];
+        if a.abs() < EPSILON {
+            // Parallel to one nappe: at most a single hit on the other
+            if b.abs() >= EPSILON {
+                candidates.push(-c / (2.0 * b));
+            }
+        } else {
+            let discriminant = b * b - 4.0 * a * c;
+            // Tangent rays can land a hair below zero in f32
+            if discriminant < -EPSILON {
+                return vec![];
+            }
+            let root = discriminant.max(0.0).sqrt();
+            candidates.push((-b - root) / (2.0 * a));
+            candidates.push((-b + root) / (2.0 * a));
+        }
+
+        let mut intersections = vec![];
+        for t in candidates {
+            let y = origin.y + t * direction.y;
+            if self.minimum < y && y < self.maximum {
+                intersections.push(Intersection::new(t, self));
+            }
+        }
+        self.intersect_caps(ray, &mut intersections);
+        intersections
+    }
+
+    fn local_normal_at(&self, point: Point, _hit: &Intersection) -> Vector {
+        let distance = point.x * point.x + point.z * point.z;
+        if distance < self.maximum * self.maximum && point.y >= self.maximum - EPSILON {
+            Vector::y_unit()
+        } else if distance < self.minimum * self.minimum && point.y <= self.minimum + EPSILON {
+            -Vector::y_unit()
+        } else {
+            let mut y = distance.sqrt();
+            if point.y > 0.0 {
+                y = -y;
+            }
+            Vector::new(point.x, y, point.z)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::SQRT_2;
+
+    use float_cmp::approx_eq;
+
+    use crate::{
+        cone::Cone, intersection::Intersection, point::Point, ray::Ray, shape::Shape,
+        vector::Vector,
+    };
+
+    #[test]
+    fn ray_strikes_cone() {
+        let cone = Cone::new();
+        let cases = [
+            (Point::new(0.0, 0.0, -5.0), Vector::z_unit(), 5.0, 5.0),
+            (
+                Point::new(0.0, 0.0, -5.0),
+                Vector::new(1.0, 1.0, 1.0),
+                8.66025,
+                8.66025,
+            ),
+            (
+                Point::new(1.0, 1.0, -5.0),
+                Vector::new(-0.5, -1.0, 1.0),
+                4.55006,
+                49.44994,
+            ),
+        ];
+        for (origin, direction, t1, t2) in cases {
+            let ray = Ray::new(origin, direction.normalize());
+            let intersections = cone.local_intersect(&ray);
+            assert_eq!(intersections.len(), 2);
+            assert!(approx_eq!(f32, intersections[0].t, t1, epsilon = 0.001));
+            assert!(approx_eq!(f32, intersections[1].t, t2, epsilon = 0.001));
+        }
+    }
+    #[test]
+    fn ray_parallel_to_one_half() {
+        let cone = Cone::new();
+        let ray = Ray::new(
+            Point::new(0.0, 0.0, -1.0),
+            Vector::new(0.0, 1.0, 1.0).normalize(),
+        );
+        let intersections = cone.local_intersect(&ray);
+        assert_eq!(intersections.len(), 1);
+        assert!(approx_eq!(
+            f32,
+            intersections[0].t,
+            0.35355,
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn intersect_caps_of_closed_cone() {
+        let cone = Cone::new().truncated(-0.5, 0.5, true);
+        let cases = [
+            (Point::new(0.0, 0.0, -5.0), Vector::y_unit(), 0),
+            (Point::new(0.0, 0.0, -0.25), Vector::new(0.0, 1.0, 1.0), 2),
+            (Point::new(0.0, 0.0, -0.25), Vector::y_unit(), 4),
+        ];
+        for (origin, direction, count) in cases {
+            let ray = Ray::new(origin, direction.normalize());
+            assert_eq!(cone.local_intersect(&ray).len(), count);
+        }
+    }
+    #[test]
+    fn closed_untruncated_cone_has_no_caps() {
+        let cone = Cone::new().truncated(f32::NEG_INFINITY, f32::INFINITY, true);
+        let ray = Ray::new(
+            Point::new(1.0, 1.0, -5.0),
+            Vector::new(-0.5, -1.0, 1.0).normalize(),
+        );
+        let intersections = cone.local_intersect(&ray);
+        assert_eq!(intersections.len(), 2);
+        assert!(intersections.iter().all(|i| i.t.is_finite()));
+    }
+    #[test]
+    fn normal_on_cone() {
+        let cone = Cone::new();
+        let cases = [
+            (Point::origin(), Vector::new(0.0, 0.0, 0.0)),
+            (Point::new(1.0, 1.0, 1.0), Vector::new(1.0, -SQRT_2, 1.0)),
+            (Point::new(-1.0, -1.0, 0.0), Vector::new(-1.0, 1.0, 0.0)),
+        ];
+        for (point, normal) in cases {
+            assert_eq!(
+                cone.local_normal_at(point, &Intersection::new(0.0, &cone)),
+                normal
+            );
+        }
+    }
+    #[test]
+    fn normal_on_cone_end_caps() {
+        let cone = Cone::new().truncated(-1.0, 1.0, true);
+        assert_eq!(
+            cone.local_normal_at(Point::new(0.5, 1.0, 0.0), &Intersection::new(0.0, &cone)),
+            Vector::y_unit()
+        );
+        assert_eq!(
+            cone.local_normal_at(Point::new(0.0, -1.0, 0.5), &Intersection::new(0.0, &cone)),
+            -Vector::y_unit()
+        );
+    }
+}
@@ -0,0 +1,286 @@
+use crate::{
+    compound,
+    intersection::{Intersection, Intersections},
+    material::Material,
+    matrix::Matrix4,
+    point::Point,
+    ray::Ray,
+    shape::Shape,
+    vector::Vector,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[allow(dead_code)]
+pub(crate) enum CsgOperation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl CsgOperation {
+    /// Whether a hit survives: `left_hit` says which operand was struck, and
+    /// `in_left`/`in_right` whether the ray was already inside each operand.
+    pub fn allows(self, left_hit: bool, in_left: bool, in_right: bool) -> bool {
+        match self {
+            CsgOperation::Union => (left_hit && !in_right) || (!left_hit && !in_left),
+            CsgOperation::Intersection => (left_hit && in_right) || (!left_hit && in_left),
+            CsgOperation::Difference => (left_hit && !in_right) || (!left_hit && in_left),
+        }
+    }
+}
+
+/// Two shapes combined by a set operation. The CSG transform is baked into
+/// both operands, as described in `compound`, and the operands' own
+/// materials are used for shading rather than the CSG's `material`.
+#[derive(Debug)]
+pub(crate) struct Csg {
+    transform: Matrix4,
+    inverse: Matrix4,
+    material: Material,
+    operation: CsgOperation,
+    left: Box<dyn Shape>,
+    right: Box<dyn Shape>,
+}
+
+impl Csg {
+    pub fn new(
+        operation: CsgOperation,
+        left: impl Shape + 'static,
+        right: impl Shape + 'static,
+    ) -> Self {
+        Csg {
+            transform: Matrix4::identity(),
+            inverse: Matrix4::identity(),
+            material: Material::default(),
+            operation,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn operation(&self) -> CsgOperation {
+        self.operation
+    }
+
+    #[allow(dead_code)]
+    pub fn left(&self) -> &dyn Shape {
+        self.left.as_ref()
+    }
+
+    #[allow(dead_code)]
+    pub fn right(&self) -> &dyn Shape {
+        self.right.as_ref()
+    }
+
+    /// Keeps only the intersections on the surface of the combined shape.
+    pub fn filter_intersections<'a>(&self, intersections: Intersections<'a>) -> Intersections<'a> {
+        let mut in_left = false;
+        let mut in_right = false;
+        let mut kept = vec![];
+        for intersection in intersections {
+            let left_hit = self.left.includes(intersection.object);
+            if self.operation.allows(left_hit, in_left, in_right) {
+                kept.push(intersection);
+            }
+            if left_hit {
+                in_left = !in_left;
+            } else {
+                in_right = !in_right;
+            }
+        }
+        Intersections::new(kept)
+    }
+}
+
+impl Shape for Csg {
+    fn transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn inverse(&self) -> Matrix4 {
+        self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix4) {
+        let operands = [&mut self.left, &mut self.right];
+        self.inverse = compound::rebase(operands, self.inverse, transform);
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        compound::local_intersect(self, ray)
+    }
+
+    fn local_normal_at(&self, point: Point, hit: &Intersection) -> Vector {
+        compound::local_normal_at(self, point, hit)
+    }
+
+    fn normal_at(&self, world_point: Point, hit: &Intersection) -> Vector {
+        compound::normal_at(self, world_point, hit)
+    }
+
+    fn includes(&self, other: &dyn Shape) -> bool {
+        self.left.includes(other) || self.right.includes(other)
+    }
+
+    /// Operands already carry the CSG transform, so they take the world ray
+    /// as-is.
+    fn intersect(&self, ray: &Ray) -> Intersections<'_> {
+        let intersections = self
+            .left
+            .intersect(ray)
+            .into_iter()
+            .chain(self.right.intersect(ray))
+            .collect();
+        self.filter_intersections(intersections)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        csg::{Csg, CsgOperation},
+        cube::Cube,
+        intersection::{Intersection, Intersections},
+        matrix::Matrix4,
+        point::Point,
+        ray::Ray,
+        shape::Shape,
+        sphere::Sphere,
+        transform::{scaling, translation},
+        vector::Vector,
+    };
+
+    #[test]
+    fn create_csg() {
+        let csg = Csg::new(CsgOperation::Union, Sphere::new(), Cube::new());
+        assert_eq!(csg.operation(), CsgOperation::Union);
+        assert_eq!(csg.transform(), Matrix4::identity());
+        assert!(csg.includes(csg.left()));
+        assert!(csg.includes(csg.right()));
+    }
+    #[test]
+    fn evaluate_rules() {
+        use CsgOperation::{Difference, Intersection, Union};
+
+        let cases = [
+            (Union, true, true, true, false),
+            (Union, true, true, false, true),
+            (Union, true, false, true, false),
+            (Union, true, false, false, true),
+            (Union, false, true, true, false),
+            (Union, false, true, false, false),
+            (Union, false, false, true, true),
+            (Union, false, false, false, true),
+            (Intersection, true, true, true, true),
+            (Intersection, true, true, false, false),
+            (Intersection, true, false, true, true),
+            (Intersection, true, false, false, false),
+            (Intersection, false, true, true, true),
+            (Intersection, false, true, false, true),
+            (Intersection, false, false, true, false),
+            (Intersection, false, false, false, false),
+            (Difference, true, true, true, false),
+            (Difference, true, true, false, true),
+            (Difference, true, false, true, false),
+            (Difference, true, false, false, true),
+            (Difference, false, true, true, true),
+            (Difference, false, true, false, true),
+            (Difference, false, false, true, false),
+            (Difference, false, false, false, false),
+        ];
+        for (operation, left_hit, in_left, in_right, allowed) in cases {
+            assert_eq!(
+                operation.allows(left_hit, in_left, in_right),
+                allowed,
+                "{operation:?} {left_hit} {in_left} {in_right}"
+            );
+        }
+    }
+    #[test]
+    fn filter_intersections() {
+        let cases = [
+            (CsgOperation::Union, 0, 3),
+            (CsgOperation::Intersection, 1, 2),
+            (CsgOperation::Difference, 0, 1),
+        ];
+        for (operation, x0, x1) in cases {
+            let csg = Csg::new(operation, Sphere::new(), Cube::new());
+            let intersections = Intersections::new(vec![
+                Intersection::new(1.0, csg.left()),
+                Intersection::new(2.0, csg.right()),
+                Intersection::new(3.0, csg.left()),
+                Intersection::new(4.0, csg.right()),
+            ]);
+            let result = csg.filter_intersections(intersections.clone());
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0], intersections[x0]);
+            assert_eq!(result[1], intersections[x1]);
+        }
+    }
+    #[test]
+    fn ray_misses_csg() {
+        let csg = Csg::new(CsgOperation::Union, Sphere::new(), Cube::new());
+        let ray = Ray::new(Point::new(0.0, 2.0, -5.0), Vector::z_unit());
+        assert!(csg.intersect(&ray).is_empty());
+    }
+    #[test]
+    fn ray_hits_csg() {
+        let csg = Csg::new(
+            CsgOperation::Union,
+            Sphere::new(),
+            Sphere::new().with_transform(translation(0.0, 0.0, 0.5)),
+        );
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let intersections = csg.intersect(&ray);
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0], Intersection::new(4.0, csg.left()));
+        assert_eq!(intersections[1], Intersection::new(6.5, csg.right()));
+    }
+    #[test]
+    fn transform_applies_to_operands() {
+        let csg = Csg::new(
+            CsgOperation::Difference,
+            Cube::new(),
+            Sphere::new().with_transform(scaling(0.5, 0.5, 0.5)),
+        )
+        .with_transform(scaling(2.0, 2.0, 2.0));
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        // Both faces of the cube, plus both walls of the hollow the sphere carves out
+        let ts: Vec<f32> = csg.intersect(&ray).iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![3.0, 4.0, 6.0, 7.0]);
+    }
+    #[test]
+    fn normal_comes_from_hit_operand() {
+        let csg = Csg::new(
+            CsgOperation::Difference,
+            Cube::new(),
+            Sphere::new().with_transform(scaling(0.5, 0.5, 0.5)),
+        )
+        .with_transform(translation(0.0, 0.0, 5.0));
+        let ray = Ray::new(Point::origin(), Vector::z_unit());
+        // The cube's front face, then the near wall of the hollow the sphere
+        // carves out, whose normal faces into the sphere
+        let hit = csg.intersect(&ray)[1];
+        assert!(std::ptr::addr_eq(hit.object, csg.right()));
+        let point = ray.position(hit.t);
+        assert_eq!(
+            csg.normal_at(point, &hit),
+            hit.object.normal_at(point, &hit)
+        );
+        let other = Sphere::new();
+        assert_eq!(
+            csg.normal_at(point, &Intersection::new(1.0, &other)),
+            Vector::zero()
+        );
+    }
+}
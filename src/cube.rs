@@ -0,0 +1,206 @@
+use crate::{
+    intersection::Intersection, material::Material, matrix::Matrix4, point::Point, ray::Ray,
+    shape::Shape, vector::Vector, EPSILON,
+};
+
+/// Axis-aligned cube spanning -1..1 on every axis.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Cube {
+    transform: Matrix4,
+    inverse: Matrix4,
+    material: Material,
+}
+
+impl Cube {
+    pub fn new() -> Self {
+        Cube {
+            transform: Matrix4::identity(),
+            inverse: Matrix4::identity(),
+            material: Material::default(),
+        }
+    }
+}
+
+impl Default for Cube {
+    fn default() -> Self {
+        Cube::new()
+    }
+}
+
+/// Where a ray enters and leaves the slab between the planes at -1 and 1
+/// along one axis, as `(tmin, tmax)`.
+fn check_axis(origin: f32, direction: f32) -> (f32, f32) {
+    let tmin_numerator = -1.0 - origin;
+    let tmax_numerator = 1.0 - origin;
+    let (tmin, tmax) = if direction.abs() >= EPSILON {
+        (tmin_numerator / direction, tmax_numerator / direction)
+    } else {
+        // Parallel to the slab: either always inside it or never
+        (
+            tmin_numerator * f32::INFINITY,
+            tmax_numerator * f32::INFINITY,
+        )
+    };
+    if tmin > tmax {
+        (tmax, tmin)
+    } else {
+        (tmin, tmax)
+    }
+}
+
+impl Shape for Cube {
+    fn transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn inverse(&self) -> Matrix4 {
+        self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix4) {
+        self.inverse = transform
+            .inverse()
+            .expect("cube transform must be invertible");
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let (xtmin, xtmax) = check_axis(ray.origin.x, ray.direction.x);
+        let (ytmin, ytmax) = check_axis(ray.origin.y, ray.direction.y);
+        let (ztmin, ztmax) = check_axis(ray.origin.z, ray.direction.z);
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+        if tmin > tmax {
+            return vec![];
+        }
+        vec![Intersection::new(tmin, self), Intersection::new(tmax, self)]
+    }
+
+    fn local_normal_at(&self, point: Point, _hit: &Intersection) -> Vector {
+        let max = point.x.abs().max(point.y.abs()).max(point.z.abs());
+        if max == point.x.abs() {
+            Vector::new(point.x, 0.0, 0.0)
+        } else if max == point.y.abs() {
+            Vector::new(0.0, point.y, 0.0)
+        } else {
+            Vector::new(0.0, 0.0, point.z)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cube::Cube, intersection::Intersection, point::Point, ray::Ray, shape::Shape,
+        vector::Vector,
+    };
+
+    #[test]
+    fn ray_intersects_cube() {
+        let cube = Cube::new();
+        let cases = [
+            (
+                Point::new(5.0, 0.5, 0.0),
+                Vector::new(-1.0, 0.0, 0.0),
+                4.0,
+                6.0,
+            ),
+            (
+                Point::new(-5.0, 0.5, 0.0),
+                Vector::new(1.0, 0.0, 0.0),
+                4.0,
+                6.0,
+            ),
+            (
+                Point::new(0.5, 5.0, 0.0),
+                Vector::new(0.0, -1.0, 0.0),
+                4.0,
+                6.0,
+            ),
+            (
+                Point::new(0.5, -5.0, 0.0),
+                Vector::new(0.0, 1.0, 0.0),
+                4.0,
+                6.0,
+            ),
+            (
+                Point::new(0.5, 0.0, 5.0),
+                Vector::new(0.0, 0.0, -1.0),
+                4.0,
+                6.0,
+            ),
+            (
+                Point::new(0.5, 0.0, -5.0),
+                Vector::new(0.0, 0.0, 1.0),
+                4.0,
+                6.0,
+            ),
+            (
+                Point::new(0.0, 0.5, 0.0),
+                Vector::new(0.0, 0.0, 1.0),
+                -1.0,
+                1.0,
+            ),
+        ];
+        for (origin, direction, t1, t2) in cases {
+            let intersections = cube.local_intersect(&Ray::new(origin, direction));
+            assert_eq!(intersections.len(), 2);
+            assert_eq!(intersections[0].t, t1);
+            assert_eq!(intersections[1].t, t2);
+        }
+    }
+    #[test]
+    fn ray_misses_cube() {
+        let cube = Cube::new();
+        let cases = [
+            (
+                Point::new(-2.0, 0.0, 0.0),
+                Vector::new(0.2673, 0.5345, 0.8018),
+            ),
+            (
+                Point::new(0.0, -2.0, 0.0),
+                Vector::new(0.8018, 0.2673, 0.5345),
+            ),
+            (
+                Point::new(0.0, 0.0, -2.0),
+                Vector::new(0.5345, 0.8018, 0.2673),
+            ),
+            (Point::new(2.0, 0.0, 2.0), Vector::new(0.0, 0.0, -1.0)),
+            (Point::new(0.0, 2.0, 2.0), Vector::new(0.0, -1.0, 0.0)),
+            (Point::new(2.0, 2.0, 0.0), Vector::new(-1.0, 0.0, 0.0)),
+        ];
+        for (origin, direction) in cases {
+            assert!(cube
+                .local_intersect(&Ray::new(origin, direction))
+                .is_empty());
+        }
+    }
+    #[test]
+    fn normal_on_surface_of_cube() {
+        let cube = Cube::new();
+        let cases = [
+            (Point::new(1.0, 0.5, -0.8), Vector::new(1.0, 0.0, 0.0)),
+            (Point::new(-1.0, -0.2, 0.9), Vector::new(-1.0, 0.0, 0.0)),
+            (Point::new(-0.4, 1.0, -0.1), Vector::new(0.0, 1.0, 0.0)),
+            (Point::new(0.3, -1.0, -0.7), Vector::new(0.0, -1.0, 0.0)),
+            (Point::new(-0.6, 0.3, 1.0), Vector::new(0.0, 0.0, 1.0)),
+            (Point::new(0.4, 0.4, -1.0), Vector::new(0.0, 0.0, -1.0)),
+            (Point::new(1.0, 1.0, 1.0), Vector::new(1.0, 0.0, 0.0)),
+            (Point::new(-1.0, -1.0, -1.0), Vector::new(-1.0, 0.0, 0.0)),
+        ];
+        for (point, normal) in cases {
+            assert_eq!(
+                cube.local_normal_at(point, &Intersection::new(0.0, &cube)),
+                normal
+            );
+        }
+    }
+}
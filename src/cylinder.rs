@@ -0,0 +1,239 @@
+use crate::{
+    intersection::Intersection, material::Material, matrix::Matrix4, point::Point, ray::Ray,
+    shape::Shape, vector::Vector, EPSILON,
+};
+
+/// Unit-radius cylinder around the y axis, optionally truncated to
+/// `minimum..maximum` (exclusive) and capped at both ends when `closed`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Cylinder {
+    transform: Matrix4,
+    inverse: Matrix4,
+    material: Material,
+    pub minimum: f32,
+    pub maximum: f32,
+    pub closed: bool,
+}
+
+impl Cylinder {
+    pub fn new() -> Self {
+        Cylinder {
+            transform: Matrix4::identity(),
+            inverse: Matrix4::identity(),
+            material: Material::default(),
+            minimum: f32::NEG_INFINITY,
+            maximum: f32::INFINITY,
+            closed: false,
+        }
+    }
+
+    pub fn truncated(mut self, minimum: f32, maximum: f32, closed: bool) -> Self {
+        self.minimum = minimum;
+        self.maximum = maximum;
+        self.closed = closed;
+        self
+    }
+
+    fn intersect_caps<'a>(&'a self, ray: &Ray, intersections: &mut Vec<Intersection<'a>>) {
+        // Caps only matter when closed and the ray isn't parallel to them
+        if !self.closed || ray.direction.y.abs() < EPSILON {
+            return;
+        }
+        for y in [self.minimum, self.maximum] {
+            let t = (y - ray.origin.y) / ray.direction.y;
+            if within_radius(ray, t) {
+                intersections.push(Intersection::new(t, self));
+            }
+        }
+    }
+}
+
+impl Default for Cylinder {
+    fn default() -> Self {
+        Cylinder::new()
+    }
+}
+
+/// Whether the ray at `t` lies within the unit radius of the y axis. The
+/// tolerance keeps rays grazing the rim from slipping through in f32.
+fn within_radius(ray: &Ray, t: f32) -> bool {
+    let x = ray.origin.x + t * ray.direction.x;
+    let z = ray.origin.z + t * ray.direction.z;
+    x * x + z * z <= 1.0 + EPSILON
+}
+
+impl Shape for Cylinder {
+    fn transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn inverse(&self) -> Matrix4 {
+        self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix4) {
+        self.inverse = transform
+            .inverse()
+            .expect("cylinder transform must be invertible");
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let mut intersections = vec![];
+        let a = ray.direction.x * ray.direction.x + ray.direction.z * ray.direction.z;
+        // Rays parallel to the y axis can only hit the caps
+        if a.abs() >= EPSILON {
+            let b = 2.0 * ray.origin.x * ray.direction.x + 2.0 * ray.origin.z * ray.direction.z;
+            let c = ray.origin.x * ray.origin.x + ray.origin.z * ray.origin.z - 1.0;
+            let discriminant = b * b - 4.0 * a * c;
+            if discriminant < 0.0 {
+                return vec![];
+            }
+            let root = discriminant.sqrt();
+            for t in [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)] {
+                let y = ray.origin.y + t * ray.direction.y;
+                if self.minimum < y && y < self.maximum {
+                    intersections.push(Intersection::new(t, self));
+                }
+            }
+        }
+        self.intersect_caps(ray, &mut intersections);
+        intersections
+    }
+
+    fn local_normal_at(&self, point: Point, _hit: &Intersection) -> Vector {
+        let distance = point.x * point.x + point.z * point.z;
+        if distance < 1.0 && point.y >= self.maximum - EPSILON {
+            Vector::y_unit()
+        } else if distance < 1.0 && point.y <= self.minimum + EPSILON {
+            -Vector::y_unit()
+        } else {
+            Vector::new(point.x, 0.0, point.z)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+
+    use crate::{
+        cylinder::Cylinder, intersection::Intersection, point::Point, ray::Ray, shape::Shape,
+        vector::Vector,
+    };
+
+    #[test]
+    fn ray_misses_cylinder() {
+        let cylinder = Cylinder::new();
+        let cases = [
+            (Point::new(1.0, 0.0, 0.0), Vector::y_unit()),
+            (Point::origin(), Vector::y_unit()),
+            (Point::new(0.0, 0.0, -5.0), Vector::new(1.0, 1.0, 1.0)),
+        ];
+        for (origin, direction) in cases {
+            let ray = Ray::new(origin, direction.normalize());
+            assert!(cylinder.local_intersect(&ray).is_empty());
+        }
+    }
+    #[test]
+    fn ray_strikes_cylinder() {
+        let cylinder = Cylinder::new();
+        let cases = [
+            (Point::new(1.0, 0.0, -5.0), Vector::z_unit(), 5.0, 5.0),
+            (Point::new(0.0, 0.0, -5.0), Vector::z_unit(), 4.0, 6.0),
+            (
+                Point::new(0.5, 0.0, -5.0),
+                Vector::new(0.1, 1.0, 1.0),
+                6.80798,
+                7.08872,
+            ),
+        ];
+        for (origin, direction, t1, t2) in cases {
+            let ray = Ray::new(origin, direction.normalize());
+            let intersections = cylinder.local_intersect(&ray);
+            assert_eq!(intersections.len(), 2);
+            assert!(approx_eq!(f32, intersections[0].t, t1, epsilon = 0.0001));
+            assert!(approx_eq!(f32, intersections[1].t, t2, epsilon = 0.0001));
+        }
+    }
+    #[test]
+    fn normal_on_cylinder() {
+        let cylinder = Cylinder::new();
+        let cases = [
+            (Point::new(1.0, 0.0, 0.0), Vector::new(1.0, 0.0, 0.0)),
+            (Point::new(0.0, 5.0, -1.0), Vector::new(0.0, 0.0, -1.0)),
+            (Point::new(0.0, -2.0, 1.0), Vector::new(0.0, 0.0, 1.0)),
+            (Point::new(-1.0, 1.0, 0.0), Vector::new(-1.0, 0.0, 0.0)),
+        ];
+        for (point, normal) in cases {
+            assert_eq!(
+                cylinder.local_normal_at(point, &Intersection::new(0.0, &cylinder)),
+                normal
+            );
+        }
+    }
+    #[test]
+    fn default_cylinder_is_infinite_and_open() {
+        let cylinder = Cylinder::new();
+        assert_eq!(cylinder.minimum, f32::NEG_INFINITY);
+        assert_eq!(cylinder.maximum, f32::INFINITY);
+        assert!(!cylinder.closed);
+    }
+    #[test]
+    fn intersect_truncated_cylinder() {
+        let cylinder = Cylinder::new().truncated(1.0, 2.0, false);
+        let cases = [
+            (Point::new(0.0, 1.5, 0.0), Vector::new(0.1, 1.0, 0.0), 0),
+            (Point::new(0.0, 3.0, -5.0), Vector::z_unit(), 0),
+            (Point::new(0.0, 0.0, -5.0), Vector::z_unit(), 0),
+            (Point::new(0.0, 2.0, -5.0), Vector::z_unit(), 0),
+            (Point::new(0.0, 1.0, -5.0), Vector::z_unit(), 0),
+            (Point::new(0.0, 1.5, -2.0), Vector::z_unit(), 2),
+        ];
+        for (origin, direction, count) in cases {
+            let ray = Ray::new(origin, direction.normalize());
+            assert_eq!(cylinder.local_intersect(&ray).len(), count);
+        }
+    }
+    #[test]
+    fn intersect_caps_of_closed_cylinder() {
+        let cylinder = Cylinder::new().truncated(1.0, 2.0, true);
+        let cases = [
+            (Point::new(0.0, 3.0, 0.0), Vector::new(0.0, -1.0, 0.0)),
+            (Point::new(0.0, 3.0, -2.0), Vector::new(0.0, -1.0, 2.0)),
+            (Point::new(0.0, 4.0, -2.0), Vector::new(0.0, -1.0, 1.0)),
+            (Point::new(0.0, 0.0, -2.0), Vector::new(0.0, 1.0, 2.0)),
+            (Point::new(0.0, -1.0, -2.0), Vector::new(0.0, 1.0, 1.0)),
+        ];
+        for (origin, direction) in cases {
+            let ray = Ray::new(origin, direction.normalize());
+            assert_eq!(cylinder.local_intersect(&ray).len(), 2);
+        }
+    }
+    #[test]
+    fn normal_on_cylinder_end_caps() {
+        let cylinder = Cylinder::new().truncated(1.0, 2.0, true);
+        let cases = [
+            (Point::new(0.0, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0)),
+            (Point::new(0.5, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0)),
+            (Point::new(0.0, 1.0, 0.5), Vector::new(0.0, -1.0, 0.0)),
+            (Point::new(0.0, 2.0, 0.0), Vector::y_unit()),
+            (Point::new(0.5, 2.0, 0.0), Vector::y_unit()),
+            (Point::new(0.0, 2.0, 0.5), Vector::y_unit()),
+        ];
+        for (point, normal) in cases {
+            assert_eq!(
+                cylinder.local_normal_at(point, &Intersection::new(0.0, &cylinder)),
+                normal
+            );
+        }
+    }
+}
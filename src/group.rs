@@ -0,0 +1,299 @@
+use crate::{
+    compound,
+    intersection::{Intersection, Intersections},
+    material::Material,
+    matrix::Matrix4,
+    point::Point,
+    ray::Ray,
+    shape::Shape,
+    vector::Vector,
+};
+
+/// A collection of shapes placed as a unit. The group's transform is baked
+/// into its children, as described in `compound`. Children keep their own
+/// materials; the group's `material` is never used for shading.
+#[derive(Debug)]
+pub(crate) struct Group {
+    transform: Matrix4,
+    inverse: Matrix4,
+    material: Material,
+    children: Vec<Box<dyn Shape>>,
+}
+
+impl Group {
+    pub fn new() -> Self {
+        Group {
+            transform: Matrix4::identity(),
+            inverse: Matrix4::identity(),
+            material: Material::default(),
+            children: vec![],
+        }
+    }
+
+    /// Adds `child`, treating its current transform as relative to the group.
+    pub fn add_child(&mut self, mut child: impl Shape + 'static) {
+        child.set_transform(self.transform * child.transform());
+        self.children.push(Box::new(child));
+    }
+
+    pub fn with_child(mut self, child: impl Shape + 'static) -> Self {
+        self.add_child(child);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn children(&self) -> &[Box<dyn Shape>] {
+        &self.children
+    }
+
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+impl Default for Group {
+    fn default() -> Self {
+        Group::new()
+    }
+}
+
+impl Shape for Group {
+    fn transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn inverse(&self) -> Matrix4 {
+        self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix4) {
+        self.inverse = compound::rebase(&mut self.children, self.inverse, transform);
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        compound::local_intersect(self, ray)
+    }
+
+    fn local_normal_at(&self, point: Point, hit: &Intersection) -> Vector {
+        compound::local_normal_at(self, point, hit)
+    }
+
+    fn normal_at(&self, world_point: Point, hit: &Intersection) -> Vector {
+        compound::normal_at(self, world_point, hit)
+    }
+
+    fn includes(&self, other: &dyn Shape) -> bool {
+        self.children.iter().any(|child| child.includes(other))
+    }
+
+    /// Children already carry the group transform, so they take the world
+    /// ray as-is.
+    fn intersect(&self, ray: &Ray) -> Intersections<'_> {
+        self.children
+            .iter()
+            .flat_map(|child| child.intersect(ray))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use float_cmp::approx_eq;
+
+    use crate::{
+        group::Group,
+        intersection::Intersection,
+        matrix::Matrix4,
+        point::Point,
+        ray::Ray,
+        shape::Shape,
+        sphere::Sphere,
+        transform::{rotation_y, scaling, translation},
+        vector::Vector,
+    };
+
+    #[test]
+    fn new_group_is_empty() {
+        let group = Group::new();
+        assert_eq!(group.transform(), Matrix4::identity());
+        assert!(group.is_empty());
+    }
+    #[test]
+    fn add_child_to_group() {
+        let group = Group::new().with_child(Sphere::new());
+        assert_eq!(group.len(), 1);
+        assert_eq!(group.children()[0].transform(), Matrix4::identity());
+    }
+    #[test]
+    fn group_includes_nested_children() {
+        let group = Group::new()
+            .with_child(Sphere::new())
+            .with_child(Group::new().with_child(Sphere::new()));
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        for intersection in group.intersect(&ray) {
+            assert!(group.includes(intersection.object));
+        }
+        assert!(!group.includes(&Sphere::new()));
+    }
+    #[test]
+    fn intersect_empty_group() {
+        let group = Group::new();
+        let ray = Ray::new(Point::origin(), Vector::z_unit());
+        assert!(group.intersect(&ray).is_empty());
+    }
+    #[test]
+    fn intersect_nonempty_group() {
+        let group = Group::new()
+            .with_child(Sphere::new())
+            .with_child(Sphere::new().with_transform(translation(0.0, 0.0, -3.0)))
+            .with_child(Sphere::new().with_transform(translation(5.0, 0.0, 0.0)));
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let intersections = group.intersect(&ray);
+        assert_eq!(intersections.len(), 4);
+        let children = group.children();
+        assert!(std::ptr::addr_eq(
+            intersections[0].object,
+            children[1].as_ref()
+        ));
+        assert!(std::ptr::addr_eq(
+            intersections[1].object,
+            children[1].as_ref()
+        ));
+        assert!(std::ptr::addr_eq(
+            intersections[2].object,
+            children[0].as_ref()
+        ));
+        assert!(std::ptr::addr_eq(
+            intersections[3].object,
+            children[0].as_ref()
+        ));
+    }
+    #[test]
+    fn intersect_transformed_group() {
+        let group = Group::new()
+            .with_transform(scaling(2.0, 2.0, 2.0))
+            .with_child(Sphere::new().with_transform(translation(5.0, 0.0, 0.0)));
+        let ray = Ray::new(Point::new(10.0, 0.0, -10.0), Vector::z_unit());
+        assert_eq!(group.intersect(&ray).len(), 2);
+    }
+    #[test]
+    fn transform_set_after_adding_children() {
+        let group = Group::new()
+            .with_child(Sphere::new().with_transform(translation(5.0, 0.0, 0.0)))
+            .with_transform(scaling(2.0, 2.0, 2.0));
+        let ray = Ray::new(Point::new(10.0, 0.0, -10.0), Vector::z_unit());
+        assert_eq!(group.intersect(&ray).len(), 2);
+        assert_eq!(
+            group.children()[0].transform(),
+            scaling(2.0, 2.0, 2.0) * translation(5.0, 0.0, 0.0)
+        );
+    }
+    #[test]
+    fn local_intersect_matches_intersect() {
+        let group = Group::new()
+            .with_transform(translation(0.0, 1.0, 0.0))
+            .with_child(Sphere::new());
+        let ray = Ray::new(Point::new(0.0, 1.0, -5.0), Vector::z_unit());
+        let local_ray = ray.transform(&group.inverse());
+        assert_eq!(group.local_intersect(&local_ray).len(), 2);
+    }
+    #[test]
+    fn world_point_to_child_object_space() {
+        let group = Group::new()
+            .with_transform(rotation_y(PI / 2.0))
+            .with_child(
+                Group::new()
+                    .with_transform(scaling(2.0, 2.0, 2.0))
+                    .with_child(Sphere::new().with_transform(translation(5.0, 0.0, 0.0))),
+            );
+        let ray = Ray::new(Point::origin(), Vector::new(0.0, 0.0, -1.0));
+        let sphere = group.intersect(&ray)[0].object;
+        assert!(approx_eq!(
+            Point,
+            sphere.world_to_object(Point::new(-2.0, 0.0, -10.0)),
+            Point::new(0.0, 0.0, -1.0),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn child_normal_to_world_space() {
+        let group = Group::new()
+            .with_transform(rotation_y(PI / 2.0))
+            .with_child(
+                Group::new()
+                    .with_transform(scaling(1.0, 2.0, 3.0))
+                    .with_child(Sphere::new().with_transform(translation(5.0, 0.0, 0.0))),
+            );
+        let ray = Ray::new(Point::origin(), Vector::new(0.0, 0.0, -1.0));
+        let sphere = group.intersect(&ray)[0].object;
+        let third = 3.0_f32.sqrt() / 3.0;
+        assert!(approx_eq!(
+            Vector,
+            sphere.normal_to_world(Vector::new(third, third, third)),
+            Vector::new(0.2857, 0.4286, -0.8571),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn normal_on_child_of_nested_groups() {
+        let group = Group::new()
+            .with_transform(rotation_y(PI / 2.0))
+            .with_child(
+                Group::new()
+                    .with_transform(scaling(1.0, 2.0, 3.0))
+                    .with_child(Sphere::new().with_transform(translation(5.0, 0.0, 0.0))),
+            );
+        let ray = Ray::new(Point::origin(), Vector::new(0.0, 0.0, -1.0));
+        let hit = group.intersect(&ray)[0];
+        assert!(approx_eq!(
+            Vector,
+            hit.object
+                .normal_at(Point::new(1.7321, 1.1547, -5.5774), &hit),
+            Vector::new(0.2857, 0.4286, -0.8571),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn normal_on_group_comes_from_hit_child() {
+        let group = Group::new()
+            .with_transform(scaling(1.0, 2.0, 1.0))
+            .with_child(Sphere::new().with_transform(translation(0.0, 0.0, 5.0)));
+        let ray = Ray::new(Point::origin(), Vector::z_unit());
+        let hit = group.intersect(&ray)[0];
+        let point = Point::new(0.0, 0.0, 4.0);
+        let expected = hit.object.normal_at(point, &hit);
+        assert_eq!(expected, Vector::new(0.0, 0.0, -1.0));
+        assert_eq!(group.normal_at(point, &hit), expected);
+        assert!(approx_eq!(
+            Vector,
+            group.normal_to_world(group.local_normal_at(group.world_to_object(point), &hit)),
+            expected,
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn normal_on_group_for_foreign_hit_is_zero() {
+        let group = Group::new().with_child(Sphere::new());
+        let other = Sphere::new();
+        let hit = Intersection::new(1.0, &other);
+        assert_eq!(group.normal_at(Point::origin(), &hit), Vector::zero());
+        assert_eq!(group.local_normal_at(Point::origin(), &hit), Vector::zero());
+    }
+}
@@ -0,0 +1,385 @@
+use float_cmp::approx_eq;
+
+use crate::{point::Point, ray::Ray, shape::Shape, vector::Vector, EPSILON};
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Intersection<'a> {
+    pub t: f32,
+    pub object: &'a dyn Shape,
+    /// Barycentric coordinates of the hit, for shapes that interpolate
+    /// across their surface; zero otherwise
+    pub u: f32,
+    pub v: f32,
+}
+
+impl<'a> Intersection<'a> {
+    pub fn new(t: f32, object: &'a dyn Shape) -> Self {
+        Intersection::with_uv(t, object, 0.0, 0.0)
+    }
+
+    pub fn with_uv(t: f32, object: &'a dyn Shape, u: f32, v: f32) -> Self {
+        Intersection { t, object, u, v }
+    }
+
+    /// `intersections` is every hit along `ray` (including this one) and is
+    /// used to work out the refractive indices on either side of the surface.
+    pub fn prepare_computations(
+        &self,
+        ray: &Ray,
+        intersections: &Intersections<'a>,
+    ) -> Computations<'a> {
+        let (n1, n2) = self.refractive_indices(intersections);
+        let point = ray.position(self.t);
+        let eye = -ray.direction;
+        let mut normal = self.object.normal_at(point, self);
+        let inside = (normal ^ eye) < 0.0;
+        if inside {
+            normal = -normal;
+        }
+        Computations {
+            t: self.t,
+            object: self.object,
+            point,
+            over_point: point + normal * EPSILON,
+            under_point: point - normal * EPSILON,
+            eye,
+            normal,
+            reflect_vector: ray.direction.reflect(normal),
+            inside,
+            n1,
+            n2,
+        }
+    }
+
+    /// Refractive indices of the material being exited (`n1`) and entered
+    /// (`n2`) at this intersection, found by tracking which objects the ray
+    /// is inside of as it walks through `intersections`.
+    fn refractive_indices(&self, intersections: &Intersections<'a>) -> (f32, f32) {
+        let outermost = |containers: &[&dyn Shape]| {
+            containers
+                .last()
+                .map_or(1.0, |object| object.material().refractive_index)
+        };
+        let mut containers: Vec<&dyn Shape> = vec![];
+        for intersection in intersections.iter() {
+            let is_hit = intersection == self;
+            let n1 = outermost(&containers);
+            match containers
+                .iter()
+                .position(|object| std::ptr::addr_eq(*object, intersection.object))
+            {
+                Some(index) => {
+                    containers.remove(index);
+                }
+                None => containers.push(intersection.object),
+            }
+            if is_hit {
+                return (n1, outermost(&containers));
+            }
+        }
+        (1.0, 1.0)
+    }
+}
+
+/// Precomputed state about a hit that shading needs.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Computations<'a> {
+    #[allow(dead_code)]
+    pub t: f32,
+    pub object: &'a dyn Shape,
+    #[allow(dead_code)]
+    pub point: Point,
+    /// `point` nudged along the normal so shadow rays don't hit the surface itself
+    pub over_point: Point,
+    /// `point` nudged below the surface, where refracted rays start
+    pub under_point: Point,
+    pub eye: Vector,
+    pub normal: Vector,
+    pub reflect_vector: Vector,
+    /// The hit is on the inside surface; `normal` has been flipped to face the eye
+    #[allow(dead_code)]
+    pub inside: bool,
+    /// Refractive index of the material being exited
+    pub n1: f32,
+    /// Refractive index of the material being entered
+    pub n2: f32,
+}
+
+impl Computations<'_> {
+    /// Schlick's approximation of the Fresnel reflectance: the fraction of
+    /// light reflected rather than refracted at this hit.
+    pub fn schlick(&self) -> f32 {
+        let mut cos = self.eye ^ self.normal;
+        if self.n1 > self.n2 {
+            let n = self.n1 / self.n2;
+            let sin2_t = n * n * (1.0 - cos * cos);
+            if sin2_t > 1.0 {
+                // Total internal reflection
+                return 1.0;
+            }
+            cos = (1.0 - sin2_t).sqrt();
+        }
+        let r0 = ((self.n1 - self.n2) / (self.n1 + self.n2)).powi(2);
+        r0 + (1.0 - r0) * (1.0 - cos).powi(5)
+    }
+}
+
+impl PartialEq for Intersection<'_> {
+    fn eq(&self, rhs: &Intersection) -> bool {
+        approx_eq!(f32, self.t, rhs.t) && std::ptr::addr_eq(self.object, rhs.object)
+    }
+}
+
+/// Intersections along a ray, kept sorted by ascending `t`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Intersections<'a> {
+    intersections: Vec<Intersection<'a>>,
+}
+
+impl<'a> Intersections<'a> {
+    pub fn new(mut intersections: Vec<Intersection<'a>>) -> Self {
+        intersections.sort_by(|a, b| a.t.total_cmp(&b.t));
+        Intersections { intersections }
+    }
+
+    /// The visible intersection: the one with the lowest non-negative `t`.
+    pub fn hit(&self) -> Option<&Intersection<'a>> {
+        self.intersections.iter().find(|i| i.t >= 0.0)
+    }
+
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.intersections.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.intersections.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Intersection<'a>> {
+        self.intersections.iter()
+    }
+}
+
+impl<'a> FromIterator<Intersection<'a>> for Intersections<'a> {
+    fn from_iter<I: IntoIterator<Item = Intersection<'a>>>(iter: I) -> Self {
+        Intersections::new(iter.into_iter().collect())
+    }
+}
+
+impl<'a> IntoIterator for Intersections<'a> {
+    type Item = Intersection<'a>;
+    type IntoIter = std::vec::IntoIter<Intersection<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intersections.into_iter()
+    }
+}
+
+impl<'a> std::ops::Index<usize> for Intersections<'a> {
+    type Output = Intersection<'a>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.intersections[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_1_SQRT_2;
+
+    use float_cmp::approx_eq;
+
+    use crate::{
+        intersection::{Intersection, Intersections},
+        plane::Plane,
+        point::Point,
+        ray::Ray,
+        shape::Shape,
+        sphere::Sphere,
+        transform::{scaling, translation},
+        vector::Vector,
+        EPSILON,
+    };
+
+    #[test]
+    fn intersection_has_t_and_object() {
+        let sphere = Sphere::new();
+        let intersection = Intersection::new(3.5, &sphere);
+        assert_eq!(intersection.t, 3.5);
+        assert!(std::ptr::addr_eq(intersection.object, &sphere));
+    }
+    #[test]
+    fn intersections_are_sorted() {
+        let sphere = Sphere::new();
+        let intersections = Intersections::new(vec![
+            Intersection::new(2.0, &sphere),
+            Intersection::new(-1.0, &sphere),
+            Intersection::new(1.0, &sphere),
+        ]);
+        assert_eq!(intersections.len(), 3);
+        assert_eq!(intersections[0].t, -1.0);
+        assert_eq!(intersections[1].t, 1.0);
+        assert_eq!(intersections[2].t, 2.0);
+    }
+    #[test]
+    fn hit_when_all_positive() {
+        let sphere = Sphere::new();
+        let i1 = Intersection::new(1.0, &sphere);
+        let i2 = Intersection::new(2.0, &sphere);
+        let intersections = Intersections::new(vec![i2, i1]);
+        assert_eq!(intersections.hit(), Some(&i1));
+    }
+    #[test]
+    fn hit_when_some_negative() {
+        let sphere = Sphere::new();
+        let i1 = Intersection::new(-1.0, &sphere);
+        let i2 = Intersection::new(1.0, &sphere);
+        let intersections = Intersections::new(vec![i2, i1]);
+        assert_eq!(intersections.hit(), Some(&i2));
+    }
+    #[test]
+    fn no_hit_when_all_negative() {
+        let sphere = Sphere::new();
+        let intersections = Intersections::new(vec![
+            Intersection::new(-2.0, &sphere),
+            Intersection::new(-1.0, &sphere),
+        ]);
+        assert_eq!(intersections.hit(), None);
+    }
+    #[test]
+    fn hit_is_lowest_non_negative() {
+        let sphere = Sphere::new();
+        let i1 = Intersection::new(5.0, &sphere);
+        let i2 = Intersection::new(7.0, &sphere);
+        let i3 = Intersection::new(-3.0, &sphere);
+        let i4 = Intersection::new(2.0, &sphere);
+        let intersections = Intersections::new(vec![i1, i2, i3, i4]);
+        assert_eq!(intersections.hit(), Some(&i4));
+    }
+    #[test]
+    fn precompute_hit_state() {
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let sphere = Sphere::new();
+        let intersections = Intersections::new(vec![Intersection::new(4.0, &sphere)]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_eq!(comps.t, intersections[0].t);
+        assert!(std::ptr::addr_eq(comps.object, &sphere));
+        assert_eq!(comps.point, Point::new(0.0, 0.0, -1.0));
+        assert_eq!(comps.eye, Vector::new(0.0, 0.0, -1.0));
+        assert_eq!(comps.normal, Vector::new(0.0, 0.0, -1.0));
+    }
+    #[test]
+    fn hit_on_outside() {
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let sphere = Sphere::new();
+        let intersections = Intersections::new(vec![Intersection::new(4.0, &sphere)]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(!comps.inside);
+    }
+    #[test]
+    fn hit_on_inside() {
+        let ray = Ray::new(Point::origin(), Vector::z_unit());
+        let sphere = Sphere::new();
+        let intersections = Intersections::new(vec![Intersection::new(1.0, &sphere)]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_eq!(comps.point, Point::new(0.0, 0.0, 1.0));
+        assert_eq!(comps.eye, Vector::new(0.0, 0.0, -1.0));
+        assert!(comps.inside);
+        assert_eq!(comps.normal, Vector::new(0.0, 0.0, -1.0));
+    }
+    #[test]
+    fn hit_offsets_over_point() {
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let sphere = Sphere::new().with_transform(translation(0.0, 0.0, 1.0));
+        let intersections = Intersections::new(vec![Intersection::new(5.0, &sphere)]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(comps.over_point.z < -EPSILON / 2.0);
+        assert!(comps.point.z > comps.over_point.z);
+    }
+    #[test]
+    fn precompute_reflection_vector() {
+        let plane = Plane::new();
+        let ray = Ray::new(
+            Point::new(0.0, 1.0, -1.0),
+            Vector::new(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let intersections = Intersections::new(vec![Intersection::new(2.0_f32.sqrt(), &plane)]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_eq!(
+            comps.reflect_vector,
+            Vector::new(0.0, FRAC_1_SQRT_2, FRAC_1_SQRT_2)
+        );
+    }
+    #[test]
+    fn refractive_indices_at_various_intersections() {
+        let mut a = Sphere::glass().with_transform(scaling(2.0, 2.0, 2.0));
+        a.material_mut().refractive_index = 1.5;
+        let mut b = Sphere::glass().with_transform(translation(0.0, 0.0, -0.25));
+        b.material_mut().refractive_index = 2.0;
+        let mut c = Sphere::glass().with_transform(translation(0.0, 0.0, 0.25));
+        c.material_mut().refractive_index = 2.5;
+        let ray = Ray::new(Point::new(0.0, 0.0, -4.0), Vector::z_unit());
+        let intersections = Intersections::new(vec![
+            Intersection::new(2.0, &a),
+            Intersection::new(2.75, &b),
+            Intersection::new(3.25, &c),
+            Intersection::new(4.75, &b),
+            Intersection::new(5.25, &c),
+            Intersection::new(6.0, &a),
+        ]);
+        let expected = [
+            (1.0, 1.5),
+            (1.5, 2.0),
+            (2.0, 2.5),
+            (2.5, 2.5),
+            (2.5, 1.5),
+            (1.5, 1.0),
+        ];
+        for (intersection, (n1, n2)) in intersections.iter().zip(expected) {
+            let comps = intersection.prepare_computations(&ray, &intersections);
+            assert_eq!(comps.n1, n1);
+            assert_eq!(comps.n2, n2);
+        }
+    }
+    #[test]
+    fn hit_offsets_under_point() {
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let sphere = Sphere::glass().with_transform(translation(0.0, 0.0, 1.0));
+        let intersections = Intersections::new(vec![Intersection::new(5.0, &sphere)]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(comps.under_point.z > EPSILON / 2.0);
+        assert!(comps.point.z < comps.under_point.z);
+    }
+    #[test]
+    fn schlick_under_total_internal_reflection() {
+        let sphere = Sphere::glass();
+        let ray = Ray::new(Point::new(0.0, 0.0, FRAC_1_SQRT_2), Vector::y_unit());
+        let intersections = Intersections::new(vec![
+            Intersection::new(-FRAC_1_SQRT_2, &sphere),
+            Intersection::new(FRAC_1_SQRT_2, &sphere),
+        ]);
+        let comps = intersections[1].prepare_computations(&ray, &intersections);
+        assert_eq!(comps.schlick(), 1.0);
+    }
+    #[test]
+    fn schlick_with_perpendicular_viewing_angle() {
+        let sphere = Sphere::glass();
+        let ray = Ray::new(Point::origin(), Vector::y_unit());
+        let intersections = Intersections::new(vec![
+            Intersection::new(-1.0, &sphere),
+            Intersection::new(1.0, &sphere),
+        ]);
+        let comps = intersections[1].prepare_computations(&ray, &intersections);
+        assert!(approx_eq!(f32, comps.schlick(), 0.04, epsilon = 0.0001));
+    }
+    #[test]
+    fn schlick_with_small_angle_and_n2_greater_than_n1() {
+        let sphere = Sphere::glass();
+        let ray = Ray::new(Point::new(0.0, 0.99, -2.0), Vector::z_unit());
+        let intersections = Intersections::new(vec![Intersection::new(1.8589, &sphere)]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(approx_eq!(f32, comps.schlick(), 0.48873, epsilon = 0.0001));
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{
+    color::{Color, BLACK},
+    material::Material,
+    point::Point,
+    shape::Shape,
+    vector::Vector,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct PointLight {
+    pub position: Point,
+    pub intensity: Color,
+}
+
+impl PointLight {
+    pub fn new(position: Point, intensity: Color) -> Self {
+        PointLight {
+            position,
+            intensity,
+        }
+    }
+}
+
+/// Phong shading of `point` on `object`'s surface with `material`, seen along
+/// `eye` with surface normal `normal`. Both vectors are expected to be
+/// normalized. A point `in_shadow` only receives the ambient term.
+pub(crate) fn lighting(
+    material: &Material,
+    object: &dyn Shape,
+    light: &PointLight,
+    point: Point,
+    eye: Vector,
+    normal: Vector,
+    in_shadow: bool,
+) -> Color {
+    let color = match &material.pattern {
+        Some(pattern) => pattern.pattern_at_shape(object, point),
+        None => material.color,
+    };
+    let effective_color = color * light.intensity;
+    let light_vector = (light.position - point).normalize();
+    let ambient = effective_color * material.ambient;
+    if in_shadow {
+        return ambient;
+    }
+
+    // A negative cosine means the light is on the other side of the surface
+    let light_dot_normal = light_vector ^ normal;
+    if light_dot_normal < 0.0 {
+        return ambient;
+    }
+    let diffuse = effective_color * material.diffuse * light_dot_normal;
+
+    let reflect_vector = (-light_vector).reflect(normal);
+    let reflect_dot_eye = reflect_vector ^ eye;
+    let specular = if reflect_dot_eye <= 0.0 {
+        BLACK
+    } else {
+        light.intensity * material.specular * reflect_dot_eye.powf(material.shininess)
+    };
+
+    ambient + diffuse + specular
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+
+    use crate::{
+        color::{Color, BLACK, WHITE},
+        light::{lighting, PointLight},
+        material::Material,
+        pattern::Stripe,
+        point::Point,
+        sphere::Sphere,
+        vector::Vector,
+    };
+
+    #[test]
+    fn point_light_has_position_and_intensity() {
+        let light = PointLight::new(Point::origin(), WHITE);
+        assert_eq!(light.position, Point::origin());
+        assert_eq!(light.intensity, WHITE);
+    }
+    #[test]
+    fn eye_between_light_and_surface() {
+        let material = Material::default();
+        let eye = Vector::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 0.0, -10.0), WHITE);
+        let result = lighting(
+            &material,
+            &Sphere::new(),
+            &light,
+            Point::origin(),
+            eye,
+            normal,
+            false,
+        );
+        assert_eq!(result, Color::new(1.9, 1.9, 1.9));
+    }
+    #[test]
+    fn eye_offset_45_degrees() {
+        let material = Material::default();
+        let eye = Vector::new(0.0, 2.0_f32.sqrt() / 2.0, -(2.0_f32.sqrt()) / 2.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 0.0, -10.0), WHITE);
+        let result = lighting(
+            &material,
+            &Sphere::new(),
+            &light,
+            Point::origin(),
+            eye,
+            normal,
+            false,
+        );
+        assert_eq!(result, Color::new(1.0, 1.0, 1.0));
+    }
+    #[test]
+    fn light_offset_45_degrees() {
+        let material = Material::default();
+        let eye = Vector::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 10.0, -10.0), WHITE);
+        let result = lighting(
+            &material,
+            &Sphere::new(),
+            &light,
+            Point::origin(),
+            eye,
+            normal,
+            false,
+        );
+        assert!(approx_eq!(
+            Color,
+            result,
+            Color::new(0.7364, 0.7364, 0.7364),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn eye_in_reflection_path() {
+        let material = Material::default();
+        let eye = Vector::new(0.0, -(2.0_f32.sqrt()) / 2.0, -(2.0_f32.sqrt()) / 2.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 10.0, -10.0), WHITE);
+        let result = lighting(
+            &material,
+            &Sphere::new(),
+            &light,
+            Point::origin(),
+            eye,
+            normal,
+            false,
+        );
+        assert!(approx_eq!(
+            Color,
+            result,
+            Color::new(1.6364, 1.6364, 1.6364),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn light_behind_surface() {
+        let material = Material::default();
+        let eye = Vector::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 0.0, 10.0), WHITE);
+        let result = lighting(
+            &material,
+            &Sphere::new(),
+            &light,
+            Point::origin(),
+            eye,
+            normal,
+            false,
+        );
+        assert_eq!(result, Color::new(0.1, 0.1, 0.1));
+    }
+    #[test]
+    fn surface_in_shadow() {
+        let material = Material::default();
+        let eye = Vector::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 0.0, -10.0), WHITE);
+        let result = lighting(
+            &material,
+            &Sphere::new(),
+            &light,
+            Point::origin(),
+            eye,
+            normal,
+            true,
+        );
+        assert_eq!(result, Color::new(0.1, 0.1, 0.1));
+    }
+    #[test]
+    fn lighting_with_pattern() {
+        let material = Material {
+            ambient: 1.0,
+            diffuse: 0.0,
+            specular: 0.0,
+            ..Material::default()
+        }
+        .with_pattern(Stripe::new(WHITE, BLACK));
+        let object = Sphere::new();
+        let eye = Vector::new(0.0, 0.0, -1.0);
+        let normal = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 0.0, -10.0), WHITE);
+        let color1 = lighting(
+            &material,
+            &object,
+            &light,
+            Point::new(0.9, 0.0, 0.0),
+            eye,
+            normal,
+            false,
+        );
+        let color2 = lighting(
+            &material,
+            &object,
+            &light,
+            Point::new(1.1, 0.0, 0.0),
+            eye,
+            normal,
+            false,
+        );
+        assert_eq!(color1, WHITE);
+        assert_eq!(color2, BLACK);
+    }
+}
@@ -1,76 +1,235 @@
-use canvas::Canvas;
-use color::WHITE;
+use std::f32::consts::PI;
+use std::io::BufWriter;
+
+use camera::{view_transform, Camera};
+use color::{Color, WHITE};
+use cone::Cone;
+use csg::{Csg, CsgOperation};
+use cube::Cube;
+use cylinder::Cylinder;
+use group::Group;
+use light::PointLight;
+use material::Material;
+use obj_file::ObjFile;
+use pattern::{Blend, Checkers, Gradient, Pattern, Ring, Stripe};
+use plane::Plane;
 use point::Point;
-use std::fmt;
+use shape::Shape;
+use sphere::Sphere;
+use tone_map::ToneMap;
+use transform::Transform;
 use vector::Vector;
+use world::World;
 
+mod camera;
 mod canvas;
 mod color;
+mod compound;
+mod cone;
+mod csg;
+mod cube;
+mod cylinder;
+mod group;
+mod intersection;
+mod light;
+mod material;
+mod matrix;
+mod obj_file;
+mod pattern;
+mod plane;
+mod png;
 mod point;
+mod ppm;
+mod ray;
+mod shape;
+mod smooth_triangle;
+mod sphere;
+mod tone_map;
+mod transform;
+mod triangle;
+// Only `Point` and `Vector` conversions use the generic tuple so far
+#[allow(dead_code)]
 mod tuple;
 mod vector;
+mod world;
 
-struct Environment {
-    gravity: Vector,
-    wind: Vector,
-}
-
-impl Environment {
-    pub fn new(gravity: Vector, wind: Vector) -> Self {
-        Environment { gravity, wind }
-    }
-}
-
-struct Projectile {
-    position: Point,
-    velocity: Vector,
-}
+/// Tolerance for offsetting points off a surface to avoid self-intersection.
+pub(crate) const EPSILON: f32 = 0.0001;
 
-impl Projectile {
-    pub fn new(position: Point, velocity: Vector) -> Self {
-        Projectile { position, velocity }
+fn main() -> std::io::Result<()> {
+    // Usage: ray-tracer-challenge [--tone-map=NAME] [--output=PATH] [MODEL.obj]
+    let mut tone_map = ToneMap::default();
+    let mut output = String::from("foo.png");
+    let mut model = None;
+    for arg in std::env::args().skip(1) {
+        if let Some(name) = arg.strip_prefix("--tone-map=") {
+            tone_map = name
+                .parse()
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+        } else if let Some(path) = arg.strip_prefix("--output=") {
+            output = path.to_string();
+        } else {
+            model = Some(arg);
+        }
     }
-}
 
-impl fmt::Display for Projectile {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "(pos: {}, vel: {})", self.position, self.velocity)
-    }
-}
+    let wall = Material {
+        color: Color::new(1.0, 0.9, 0.9),
+        specular: 0.0,
+        ..Material::default()
+    };
+    let floor = Plane::new().with_material(
+        wall.clone()
+            .with_pattern(Checkers::new(wall.color, Color::new(0.5, 0.45, 0.45))),
+    );
+    let left_wall = Plane::new()
+        .with_transform(
+            Transform::identity()
+                .rotate_x(PI / 2.0)
+                .rotate_y(-PI / 4.0)
+                .translate(0.0, 0.0, 5.0)
+                .build(),
+        )
+        .with_material(
+            wall.clone().with_pattern(
+                Stripe::new(wall.color, Color::new(0.9, 0.8, 0.8)).with_transform(
+                    Transform::identity()
+                        .scale(0.25, 1.0, 1.0)
+                        .rotate_y(PI / 4.0)
+                        .build(),
+                ),
+            ),
+        );
+    let right_wall = Plane::new()
+        .with_transform(
+            Transform::identity()
+                .rotate_x(PI / 2.0)
+                .rotate_y(PI / 4.0)
+                .translate(0.0, 0.0, 5.0)
+                .build(),
+        )
+        .with_material(
+            wall.clone().with_pattern(Blend::new(
+                Ring::new(wall.color, Color::new(0.8, 0.8, 0.9)),
+                Gradient::new(Color::new(0.9, 0.9, 1.0), wall.color)
+                    .with_transform(Transform::identity().scale(8.0, 1.0, 1.0).build()),
+            )),
+        );
 
-fn tick(environment: &Environment, projectile: Projectile) -> Projectile {
-    let new_position = projectile.position + projectile.velocity;
-    let new_velocity = projectile.velocity + environment.gravity + environment.wind;
-    let new_projectile = Projectile::new(new_position, new_velocity);
-    new_projectile
-}
+    let middle = Sphere::new()
+        .with_transform(Transform::identity().translate(-0.5, 1.0, 0.5).build())
+        .with_material(Material {
+            color: Color::new(0.1, 1.0, 0.5),
+            diffuse: 0.7,
+            specular: 0.3,
+            ..Material::default()
+        });
+    let right = Sphere::new()
+        .with_transform(
+            Transform::identity()
+                .scale(0.5, 0.5, 0.5)
+                .translate(1.5, 0.5, -0.5)
+                .build(),
+        )
+        .with_material(Material {
+            color: Color::new(0.5, 1.0, 0.1),
+            diffuse: 0.7,
+            specular: 0.3,
+            ..Material::default()
+        });
+    let left = Sphere::new()
+        .with_transform(
+            Transform::identity()
+                .scale(0.33, 0.33, 0.33)
+                .translate(-1.5, 0.33, -0.75)
+                .build(),
+        )
+        .with_material(Material {
+            color: Color::new(1.0, 0.8, 0.1),
+            diffuse: 0.7,
+            specular: 0.3,
+            ..Material::default()
+        });
 
-fn main() {
-    let canvas_height: usize = 550;
-    let canvas_width: usize = 900;
-    let mut canvas = Canvas::new(canvas_width, canvas_height);
-    let mut projectile = Projectile::new(
-        Point::new(0.0, 1.0, 0.0),
-        Vector::new(1.0, 1.8, 0.0).normalize() * 11.25,
+    // A die: a cube with its corners rounded off by a sphere
+    let red = Material {
+        color: Color::new(0.9, 0.2, 0.2),
+        ..Material::default()
+    };
+    let die = Csg::new(
+        CsgOperation::Intersection,
+        Cube::new().with_material(red.clone()),
+        Sphere::new()
+            .with_transform(Transform::identity().scale(1.4, 1.4, 1.4).build())
+            .with_material(red),
+    )
+    .with_transform(
+        Transform::identity()
+            .scale(0.3, 0.3, 0.3)
+            .rotate_y(PI / 6.0)
+            .translate(0.7, 0.3, -1.1)
+            .build(),
     );
-    let environment = Environment::new(Vector::new(0.0, -0.1, 0.0), Vector::new(-0.01, 0.0, 0.0));
 
-    let mut height = projectile.position.y;
-    canvas.write_pixel(
-        projectile.position.x as usize,
-        canvas_height - height as usize,
-        WHITE,
-    );
+    // A lamp: a thin post under a conical shade
+    let brass = Material {
+        color: Color::new(0.8, 0.6, 0.2),
+        shininess: 300.0,
+        ..Material::default()
+    };
+    let lamp = Group::new()
+        .with_child(
+            Cylinder::new()
+                .truncated(0.0, 1.0, true)
+                .with_transform(Transform::identity().scale(0.05, 1.6, 0.05).build())
+                .with_material(brass.clone()),
+        )
+        .with_child(
+            Cone::new()
+                .truncated(-1.0, 0.0, true)
+                .with_transform(
+                    Transform::identity()
+                        .scale(0.4, 0.5, 0.4)
+                        .translate(0.0, 2.0, 0.0)
+                        .build(),
+                )
+                .with_material(brass),
+        )
+        .with_transform(Transform::identity().translate(-2.5, 0.0, 1.5).build());
 
-    while height > 0.0 {
-        projectile = tick(&environment, projectile);
-        height = projectile.position.y;
-        canvas.write_pixel(
-            projectile.position.x as usize,
-            canvas_height - height as usize,
-            WHITE,
-        );
+    let mut world = World {
+        objects: vec![
+            Box::new(floor),
+            Box::new(left_wall),
+            Box::new(right_wall),
+            Box::new(middle),
+            Box::new(right),
+            Box::new(left),
+            Box::new(die),
+            Box::new(lamp),
+        ],
+        lights: vec![PointLight::new(Point::new(-10.0, 10.0, -10.0), WHITE)],
+        ..World::new()
+    };
+    // An OBJ model given on the command line is dropped into the scene as-is
+    if let Some(path) = model {
+        let obj = ObjFile::open(&path)?;
+        if obj.ignored > 0 {
+            eprintln!("{path}: ignored {} unrecognized lines", obj.ignored);
+        }
+        world.objects.push(Box::new(obj.into_group()));
     }
+    let camera = Camera::new(900, 550, PI / 3.0).with_transform(view_transform(
+        Point::new(0.0, 1.5, -5.0),
+        Point::new(0.0, 1.0, 0.0),
+        Vector::y_unit(),
+    ));
 
-    canvas.to_ppm().unwrap();
+    let canvas = camera.render(&world).with_tone_map(tone_map);
+    // `-` streams plain PPM to stdout, for piping into other tools
+    if output == "-" {
+        canvas.write_ppm(BufWriter::new(std::io::stdout().lock()))
+    } else {
+        canvas.save(output)
+    }
 }
@@ -0,0 +1,100 @@
+use std::rc::Rc;
+
+use crate::{
+    color::{Color, WHITE},
+    pattern::Pattern,
+};
+
+/// Phong reflection model parameters.
+#[derive(Clone, Debug)]
+pub(crate) struct Material {
+    pub color: Color,
+    /// Used in place of `color` when set
+    pub pattern: Option<Rc<dyn Pattern>>,
+    pub ambient: f32,
+    pub diffuse: f32,
+    pub specular: f32,
+    pub shininess: f32,
+    /// 0.0 is matte, 1.0 a perfect mirror
+    pub reflective: f32,
+    /// 0.0 is opaque, 1.0 fully transparent
+    pub transparency: f32,
+    /// 1.0 for vacuum, ~1.33 water, ~1.5 glass
+    pub refractive_index: f32,
+}
+
+impl Material {
+    pub fn new(color: Color, ambient: f32, diffuse: f32, specular: f32, shininess: f32) -> Self {
+        Material {
+            color,
+            pattern: None,
+            ambient,
+            diffuse,
+            specular,
+            shininess,
+            reflective: 0.0,
+            transparency: 0.0,
+            refractive_index: 1.0,
+        }
+    }
+
+    pub fn with_pattern(mut self, pattern: impl Pattern + 'static) -> Self {
+        self.pattern = Some(Rc::new(pattern));
+        self
+    }
+}
+
+impl PartialEq for Material {
+    fn eq(&self, rhs: &Material) -> bool {
+        let same_pattern = match (&self.pattern, &rhs.pattern) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        same_pattern
+            && self.color == rhs.color
+            && self.ambient == rhs.ambient
+            && self.diffuse == rhs.diffuse
+            && self.specular == rhs.specular
+            && self.shininess == rhs.shininess
+            && self.reflective == rhs.reflective
+            && self.transparency == rhs.transparency
+            && self.refractive_index == rhs.refractive_index
+    }
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Material::new(WHITE, 0.1, 0.9, 0.9, 200.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        color::{BLACK, WHITE},
+        material::Material,
+        pattern::Stripe,
+    };
+
+    #[test]
+    fn default_material() {
+        let material = Material::default();
+        assert_eq!(material.color, WHITE);
+        assert!(material.pattern.is_none());
+        assert_eq!(material.ambient, 0.1);
+        assert_eq!(material.diffuse, 0.9);
+        assert_eq!(material.specular, 0.9);
+        assert_eq!(material.shininess, 200.0);
+        assert_eq!(material.reflective, 0.0);
+        assert_eq!(material.transparency, 0.0);
+        assert_eq!(material.refractive_index, 1.0);
+    }
+    #[test]
+    fn material_with_pattern() {
+        let material = Material::default().with_pattern(Stripe::new(WHITE, BLACK));
+        assert!(material.pattern.is_some());
+        assert_eq!(material.clone(), material);
+        assert_ne!(material, Material::default());
+    }
+}
@@ -0,0 +1,497 @@
+use float_cmp::{approx_eq, ApproxEq, F32Margin};
+
+use crate::{point::Point, vector::Vector};
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Matrix2 {
+    m: [[f32; 2]; 2],
+}
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Matrix3 {
+    m: [[f32; 3]; 3],
+}
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Matrix4 {
+    m: [[f32; 4]; 4],
+}
+
+impl Matrix2 {
+    pub fn new(m: [[f32; 2]; 2]) -> Self {
+        Matrix2 { m }
+    }
+
+    pub fn determinant(&self) -> f32 {
+        self.m[0][0] * self.m[1][1] - self.m[0][1] * self.m[1][0]
+    }
+}
+
+impl Matrix3 {
+    pub fn new(m: [[f32; 3]; 3]) -> Self {
+        Matrix3 { m }
+    }
+
+    pub fn submatrix(&self, row: usize, col: usize) -> Matrix2 {
+        let mut m = [[0.0; 2]; 2];
+        for (i, r) in (0..3).filter(|&r| r != row).enumerate() {
+            for (j, c) in (0..3).filter(|&c| c != col).enumerate() {
+                m[i][j] = self.m[r][c];
+            }
+        }
+        Matrix2::new(m)
+    }
+
+    pub fn minor(&self, row: usize, col: usize) -> f32 {
+        self.submatrix(row, col).determinant()
+    }
+
+    pub fn cofactor(&self, row: usize, col: usize) -> f32 {
+        let minor = self.minor(row, col);
+        if (row + col).is_multiple_of(2) {
+            minor
+        } else {
+            -minor
+        }
+    }
+
+    pub fn determinant(&self) -> f32 {
+        (0..3)
+            .map(|col| self.m[0][col] * self.cofactor(0, col))
+            .sum()
+    }
+}
+
+impl Matrix4 {
+    pub fn new(m: [[f32; 4]; 4]) -> Self {
+        Matrix4 { m }
+    }
+
+    pub fn identity() -> Self {
+        Matrix4::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut m = [[0.0; 4]; 4];
+        for (row, values) in m.iter_mut().enumerate() {
+            for (col, value) in values.iter_mut().enumerate() {
+                *value = self.m[col][row];
+            }
+        }
+        Matrix4::new(m)
+    }
+
+    pub fn submatrix(&self, row: usize, col: usize) -> Matrix3 {
+        let mut m = [[0.0; 3]; 3];
+        for (i, r) in (0..4).filter(|&r| r != row).enumerate() {
+            for (j, c) in (0..4).filter(|&c| c != col).enumerate() {
+                m[i][j] = self.m[r][c];
+            }
+        }
+        Matrix3::new(m)
+    }
+
+    pub fn minor(&self, row: usize, col: usize) -> f32 {
+        self.submatrix(row, col).determinant()
+    }
+
+    pub fn cofactor(&self, row: usize, col: usize) -> f32 {
+        let minor = self.minor(row, col);
+        if (row + col).is_multiple_of(2) {
+            minor
+        } else {
+            -minor
+        }
+    }
+
+    pub fn determinant(&self) -> f32 {
+        (0..4)
+            .map(|col| self.m[0][col] * self.cofactor(0, col))
+            .sum()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_invertible(&self) -> bool {
+        self.determinant() != 0.0
+    }
+
+    /// Returns `None` for singular matrices (determinant of zero).
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+        if determinant == 0.0 {
+            return None;
+        }
+        let mut m = [[0.0; 4]; 4];
+        for (row, values) in m.iter_mut().enumerate() {
+            for (col, value) in values.iter_mut().enumerate() {
+                // Transposed on the way in: cofactor(col, row) lands at [row][col]
+                *value = self.cofactor(col, row) / determinant;
+            }
+        }
+        Some(Matrix4::new(m))
+    }
+}
+
+impl std::ops::Index<(usize, usize)> for Matrix2 {
+    type Output = f32;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.m[row][col]
+    }
+}
+
+impl std::ops::Index<(usize, usize)> for Matrix3 {
+    type Output = f32;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.m[row][col]
+    }
+}
+
+impl std::ops::Index<(usize, usize)> for Matrix4 {
+    type Output = f32;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.m[row][col]
+    }
+}
+
+impl PartialEq for Matrix2 {
+    fn eq(&self, rhs: &Matrix2) -> bool {
+        self.m
+            .iter()
+            .flatten()
+            .zip(rhs.m.iter().flatten())
+            .all(|(a, b)| approx_eq!(f32, *a, *b))
+    }
+}
+
+impl PartialEq for Matrix3 {
+    fn eq(&self, rhs: &Matrix3) -> bool {
+        self.m
+            .iter()
+            .flatten()
+            .zip(rhs.m.iter().flatten())
+            .all(|(a, b)| approx_eq!(f32, *a, *b))
+    }
+}
+
+impl PartialEq for Matrix4 {
+    fn eq(&self, rhs: &Matrix4) -> bool {
+        self.approx_eq(*rhs, F32Margin::default())
+    }
+}
+
+impl ApproxEq for Matrix4 {
+    type Margin = F32Margin;
+
+    fn approx_eq<M: Into<Self::Margin>>(self, other: Self, margin: M) -> bool {
+        let margin = margin.into();
+        self.m
+            .iter()
+            .flatten()
+            .zip(other.m.iter().flatten())
+            .all(|(a, b)| a.approx_eq(*b, margin))
+    }
+}
+
+impl std::ops::Mul<Matrix4> for Matrix4 {
+    type Output = Matrix4;
+
+    fn mul(self, rhs: Matrix4) -> Self::Output {
+        let mut m = [[0.0; 4]; 4];
+        for (row, values) in m.iter_mut().enumerate() {
+            for (col, value) in values.iter_mut().enumerate() {
+                *value = (0..4).map(|i| self.m[row][i] * rhs.m[i][col]).sum();
+            }
+        }
+        Matrix4::new(m)
+    }
+}
+
+impl std::ops::Mul<Point> for Matrix4 {
+    type Output = Point;
+
+    fn mul(self, rhs: Point) -> Self::Output {
+        let row = |r: [f32; 4]| r[0] * rhs.x + r[1] * rhs.y + r[2] * rhs.z + r[3] * rhs.w;
+        Point::new(row(self.m[0]), row(self.m[1]), row(self.m[2]))
+    }
+}
+
+impl std::ops::Mul<Vector> for Matrix4 {
+    type Output = Vector;
+
+    fn mul(self, rhs: Vector) -> Self::Output {
+        let row = |r: [f32; 4]| r[0] * rhs.x + r[1] * rhs.y + r[2] * rhs.z + r[3] * rhs.w;
+        Vector::new(row(self.m[0]), row(self.m[1]), row(self.m[2]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+
+    use crate::{
+        matrix::{Matrix2, Matrix3, Matrix4},
+        point::Point,
+        vector::Vector,
+    };
+
+    #[test]
+    fn construct_matrix4() {
+        let matrix = Matrix4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.5, 6.5, 7.5, 8.5],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.5, 14.5, 15.5, 16.5],
+        ]);
+        assert_eq!(matrix[(0, 0)], 1.0);
+        assert_eq!(matrix[(0, 3)], 4.0);
+        assert_eq!(matrix[(1, 0)], 5.5);
+        assert_eq!(matrix[(1, 2)], 7.5);
+        assert_eq!(matrix[(2, 2)], 11.0);
+        assert_eq!(matrix[(3, 0)], 13.5);
+        assert_eq!(matrix[(3, 2)], 15.5);
+    }
+    #[test]
+    fn construct_matrix2_and_matrix3() {
+        let matrix2 = Matrix2::new([[-3.0, 5.0], [1.0, -2.0]]);
+        assert_eq!(matrix2[(0, 1)], 5.0);
+        assert_eq!(matrix2[(1, 1)], -2.0);
+        let matrix3 = Matrix3::new([[-3.0, 5.0, 0.0], [1.0, -2.0, -7.0], [0.0, 1.0, 1.0]]);
+        assert_eq!(matrix3[(0, 0)], -3.0);
+        assert_eq!(matrix3[(1, 1)], -2.0);
+        assert_eq!(matrix3[(2, 2)], 1.0);
+    }
+    #[test]
+    fn two_matrices_approx_eq() {
+        let matrix1 = Matrix4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 8.0, 7.0, 6.0],
+            [5.0, 4.0, 3.0, 0.1 + 0.2],
+        ]);
+        let matrix2 = Matrix4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 8.0, 7.0, 6.0],
+            [5.0, 4.0, 3.0, 0.3],
+        ]);
+        assert_eq!(matrix1, matrix2);
+    }
+    #[test]
+    fn two_matrices_approx_ne() {
+        let matrix1 = Matrix4::identity();
+        let matrix2 = Matrix4::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.000001],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        assert_ne!(matrix1, matrix2);
+    }
+    #[test]
+    fn multiply_two_matrices() {
+        let matrix1 = Matrix4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 8.0, 7.0, 6.0],
+            [5.0, 4.0, 3.0, 2.0],
+        ]);
+        let matrix2 = Matrix4::new([
+            [-2.0, 1.0, 2.0, 3.0],
+            [3.0, 2.0, 1.0, -1.0],
+            [4.0, 3.0, 6.0, 5.0],
+            [1.0, 2.0, 7.0, 8.0],
+        ]);
+        assert_eq!(
+            matrix1 * matrix2,
+            Matrix4::new([
+                [20.0, 22.0, 50.0, 48.0],
+                [44.0, 54.0, 114.0, 108.0],
+                [40.0, 58.0, 110.0, 102.0],
+                [16.0, 26.0, 46.0, 42.0],
+            ])
+        );
+    }
+    #[test]
+    fn multiply_matrix_by_point() {
+        let matrix = Matrix4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [2.0, 4.0, 4.0, 2.0],
+            [8.0, 6.0, 4.0, 1.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        let new_point = matrix * Point::new(1.0, 2.0, 3.0);
+        assert_eq!(new_point, Point::new(18.0, 24.0, 33.0));
+        assert_eq!(new_point.w, 1.0);
+    }
+    #[test]
+    fn multiply_matrix_by_vector() {
+        let matrix = Matrix4::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [2.0, 4.0, 4.0, 2.0],
+            [8.0, 6.0, 4.0, 1.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        let new_vector = matrix * Vector::new(1.0, 2.0, 3.0);
+        assert_eq!(new_vector, Vector::new(14.0, 22.0, 32.0));
+        assert_eq!(new_vector.w, 0.0);
+    }
+    #[test]
+    fn multiply_by_identity() {
+        let matrix = Matrix4::new([
+            [0.0, 1.0, 2.0, 4.0],
+            [1.0, 2.0, 4.0, 8.0],
+            [2.0, 4.0, 8.0, 16.0],
+            [4.0, 8.0, 16.0, 32.0],
+        ]);
+        assert_eq!(matrix * Matrix4::identity(), matrix);
+        assert_eq!(
+            Matrix4::identity() * Point::new(1.0, 2.0, 3.0),
+            Point::new(1.0, 2.0, 3.0)
+        );
+    }
+    #[test]
+    fn transpose_matrix() {
+        let matrix = Matrix4::new([
+            [0.0, 9.0, 3.0, 0.0],
+            [9.0, 8.0, 0.0, 8.0],
+            [1.0, 8.0, 5.0, 3.0],
+            [0.0, 0.0, 5.0, 8.0],
+        ]);
+        assert_eq!(
+            matrix.transpose(),
+            Matrix4::new([
+                [0.0, 9.0, 1.0, 0.0],
+                [9.0, 8.0, 8.0, 0.0],
+                [3.0, 0.0, 5.0, 5.0],
+                [0.0, 8.0, 3.0, 8.0],
+            ])
+        );
+        assert_eq!(Matrix4::identity().transpose(), Matrix4::identity());
+    }
+    #[test]
+    fn matrix2_determinant() {
+        let matrix = Matrix2::new([[1.0, 5.0], [-3.0, 2.0]]);
+        assert_eq!(matrix.determinant(), 17.0);
+    }
+    #[test]
+    fn submatrices() {
+        let matrix3 = Matrix3::new([[1.0, 5.0, 0.0], [-3.0, 2.0, 7.0], [0.0, 6.0, -3.0]]);
+        assert_eq!(
+            matrix3.submatrix(0, 2),
+            Matrix2::new([[-3.0, 2.0], [0.0, 6.0]])
+        );
+        let matrix4 = Matrix4::new([
+            [-6.0, 1.0, 1.0, 6.0],
+            [-8.0, 5.0, 8.0, 6.0],
+            [-1.0, 0.0, 8.0, 2.0],
+            [-7.0, 1.0, -1.0, 1.0],
+        ]);
+        assert_eq!(
+            matrix4.submatrix(2, 1),
+            Matrix3::new([[-6.0, 1.0, 6.0], [-8.0, 8.0, 6.0], [-7.0, -1.0, 1.0]])
+        );
+    }
+    #[test]
+    fn matrix3_minor_and_cofactor() {
+        let matrix = Matrix3::new([[3.0, 5.0, 0.0], [2.0, -1.0, -7.0], [6.0, -1.0, 5.0]]);
+        assert_eq!(matrix.minor(0, 0), -12.0);
+        assert_eq!(matrix.cofactor(0, 0), -12.0);
+        assert_eq!(matrix.minor(1, 0), 25.0);
+        assert_eq!(matrix.cofactor(1, 0), -25.0);
+    }
+    #[test]
+    fn matrix3_determinant() {
+        let matrix = Matrix3::new([[1.0, 2.0, 6.0], [-5.0, 8.0, -4.0], [2.0, 6.0, 4.0]]);
+        assert_eq!(matrix.cofactor(0, 0), 56.0);
+        assert_eq!(matrix.cofactor(0, 1), 12.0);
+        assert_eq!(matrix.cofactor(0, 2), -46.0);
+        assert_eq!(matrix.determinant(), -196.0);
+    }
+    #[test]
+    fn matrix4_determinant() {
+        let matrix = Matrix4::new([
+            [-2.0, -8.0, 3.0, 5.0],
+            [-3.0, 1.0, 7.0, 3.0],
+            [1.0, 2.0, -9.0, 6.0],
+            [-6.0, 7.0, 7.0, -9.0],
+        ]);
+        assert_eq!(matrix.cofactor(0, 0), 690.0);
+        assert_eq!(matrix.cofactor(0, 1), 447.0);
+        assert_eq!(matrix.cofactor(0, 2), 210.0);
+        assert_eq!(matrix.cofactor(0, 3), 51.0);
+        assert_eq!(matrix.determinant(), -4071.0);
+    }
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        let invertible = Matrix4::new([
+            [6.0, 4.0, 4.0, 4.0],
+            [5.0, 5.0, 7.0, 6.0],
+            [4.0, -9.0, 3.0, -7.0],
+            [9.0, 1.0, 7.0, -6.0],
+        ]);
+        let singular = Matrix4::new([
+            [-4.0, 2.0, -2.0, -3.0],
+            [9.0, 6.0, 2.0, 6.0],
+            [0.0, -5.0, 1.0, -5.0],
+            [0.0, 0.0, 0.0, 0.0],
+        ]);
+        assert!(invertible.is_invertible());
+        assert!(invertible.inverse().is_some());
+        assert!(!singular.is_invertible());
+        assert!(singular.inverse().is_none());
+    }
+    #[test]
+    fn matrix4_inverse() {
+        let matrix = Matrix4::new([
+            [-5.0, 2.0, 6.0, -8.0],
+            [1.0, -5.0, 1.0, 8.0],
+            [7.0, 7.0, -6.0, -7.0],
+            [1.0, -3.0, 7.0, 4.0],
+        ]);
+        let inverse = matrix.inverse().unwrap();
+        assert_eq!(matrix.determinant(), 532.0);
+        assert_eq!(inverse[(3, 2)], -160.0 / 532.0);
+        assert_eq!(inverse[(2, 3)], 105.0 / 532.0);
+        assert!(approx_eq!(
+            Matrix4,
+            inverse,
+            Matrix4::new([
+                [0.21805, 0.45113, 0.24060, -0.04511],
+                [-0.80827, -1.45677, -0.44361, 0.52068],
+                [-0.07895, -0.22368, -0.05263, 0.19737],
+                [-0.52256, -0.81391, -0.30075, 0.30639],
+            ]),
+            epsilon = 0.00001
+        ));
+    }
+    #[test]
+    fn multiply_product_by_inverse() {
+        let matrix1 = Matrix4::new([
+            [3.0, -9.0, 7.0, 3.0],
+            [3.0, -8.0, 2.0, -9.0],
+            [-4.0, 4.0, 4.0, 1.0],
+            [-6.0, 5.0, -1.0, 1.0],
+        ]);
+        let matrix2 = Matrix4::new([
+            [8.0, 2.0, 2.0, 2.0],
+            [3.0, -1.0, 7.0, 0.0],
+            [7.0, 0.0, 5.0, 4.0],
+            [6.0, -2.0, 0.0, 5.0],
+        ]);
+        let product = matrix1 * matrix2;
+        assert!(approx_eq!(
+            Matrix4,
+            product * matrix2.inverse().unwrap(),
+            matrix1,
+            epsilon = 0.0001
+        ));
+    }
+}
@@ -0,0 +1,419 @@
+use std::{fs, io, path::Path};
+
+use crate::{
+    group::Group, point::Point, smooth_triangle::SmoothTriangle, triangle::Triangle, vector::Vector,
+};
+
+/// A triangle read from a face line: smooth when the face named normals for
+/// its vertices, flat otherwise.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Face {
+    Flat(Triangle),
+    Smooth(SmoothTriangle),
+}
+
+/// Faces collected under a `g` statement; `name` is `None` for faces that
+/// appear before any group.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ObjGroup {
+    pub name: Option<String>,
+    pub faces: Vec<Face>,
+}
+
+/// A parsed Wavefront OBJ file. Only vertices (`v`), vertex normals (`vn`),
+/// faces (`f`) and groups (`g`) are understood; every other line, including
+/// malformed ones, is skipped and counted in `ignored`. Blank lines and `#`
+/// comments are skipped without being counted. Components after a vertex's
+/// x, y and z, such as `w` or a vertex color, are discarded.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ObjFile {
+    pub vertices: Vec<Point>,
+    pub normals: Vec<Vector>,
+    /// Always starts with the unnamed default group
+    pub groups: Vec<ObjGroup>,
+    pub ignored: usize,
+}
+
+impl ObjFile {
+    pub fn parse(source: &str) -> Self {
+        let mut obj = ObjFile {
+            groups: vec![ObjGroup::default()],
+            ..ObjFile::default()
+        };
+        for line in source.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if !obj.parse_line(line) {
+                obj.ignored += 1;
+            }
+        }
+        obj
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(ObjFile::parse(&fs::read_to_string(path)?))
+    }
+
+    /// Converts the faces into shapes, with each named group as a subgroup.
+    pub fn into_group(self) -> Group {
+        let mut root = Group::new();
+        for obj_group in self.groups {
+            match obj_group.name {
+                Some(_) => {
+                    let mut group = Group::new();
+                    add_faces(&mut group, obj_group.faces);
+                    root.add_child(group);
+                }
+                None => add_faces(&mut root, obj_group.faces),
+            }
+        }
+        root
+    }
+
+    /// Returns whether the line was understood.
+    fn parse_line(&mut self, line: &str) -> bool {
+        let mut words = line.split_whitespace();
+        let keyword = words.next();
+        let args: Vec<&str> = words.collect();
+        match keyword {
+            // Keep `v x y z w` and `v x y z r g b` lines, or every later
+            // face index would be off by one
+            Some("v") if args.len() >= 3 => match parse_floats(&args[..3]) {
+                Some([x, y, z]) => {
+                    self.vertices.push(Point::new(x, y, z));
+                    true
+                }
+                None => false,
+            },
+            Some("vn") => match parse_floats(&args) {
+                Some([x, y, z]) => {
+                    self.normals.push(Vector::new(x, y, z));
+                    true
+                }
+                None => false,
+            },
+            Some("f") => match self.parse_face(&args) {
+                Some(faces) => {
+                    self.current_group().faces.extend(faces);
+                    true
+                }
+                None => false,
+            },
+            Some("g") if !args.is_empty() => {
+                self.groups.push(ObjGroup {
+                    name: Some(args.join(" ")),
+                    faces: vec![],
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Fan-triangulates a face of three or more `v`, `v/vt`, `v//vn` or
+    /// `v/vt/vn` references.
+    fn parse_face(&self, args: &[&str]) -> Option<Vec<Face>> {
+        if args.len() < 3 {
+            return None;
+        }
+        let corners = args
+            .iter()
+            .map(|arg| self.parse_corner(arg))
+            .collect::<Option<Vec<_>>>()?;
+        let (first, rest) = corners.split_first()?;
+        let faces = rest
+            .windows(2)
+            .map(|pair| {
+                let (p1, n1) = *first;
+                let (p2, n2) = pair[0];
+                let (p3, n3) = pair[1];
+                match (n1, n2, n3) {
+                    (Some(n1), Some(n2), Some(n3)) => {
+                        Face::Smooth(SmoothTriangle::new(p1, p2, p3, n1, n2, n3))
+                    }
+                    _ => Face::Flat(Triangle::new(p1, p2, p3)),
+                }
+            })
+            .collect();
+        Some(faces)
+    }
+
+    fn parse_corner(&self, arg: &str) -> Option<(Point, Option<Vector>)> {
+        let mut indices = arg.split('/');
+        let vertex = self.vertices[parse_index(indices.next()?, self.vertices.len())?];
+        let normal = match indices.nth(1) {
+            Some(index) => Some(self.normals[parse_index(index, self.normals.len())?]),
+            None => None,
+        };
+        Some((vertex, normal))
+    }
+
+    fn current_group(&mut self) -> &mut ObjGroup {
+        self.groups
+            .last_mut()
+            .expect("there is always a default group")
+    }
+}
+
+fn add_faces(group: &mut Group, faces: Vec<Face>) {
+    for face in faces {
+        match face {
+            Face::Flat(triangle) => group.add_child(triangle),
+            Face::Smooth(triangle) => group.add_child(triangle),
+        }
+    }
+}
+
+fn parse_floats(args: &[&str]) -> Option<[f32; 3]> {
+    match args {
+        [x, y, z] => Some([x.parse().ok()?, y.parse().ok()?, z.parse().ok()?]),
+        _ => None,
+    }
+}
+
+/// OBJ indices are 1-based, or negative to count back from the last of the
+/// `count` elements read so far. Returns `None` for indices out of range.
+fn parse_index(arg: &str, count: usize) -> Option<usize> {
+    let index = arg.parse::<isize>().ok()?;
+    let index = if index < 0 {
+        count.checked_sub(index.unsigned_abs())?
+    } else {
+        index.unsigned_abs().checked_sub(1)?
+    };
+    (index < count).then_some(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        obj_file::{Face, ObjFile},
+        point::Point,
+        ray::Ray,
+        shape::Shape,
+        smooth_triangle::SmoothTriangle,
+        triangle::Triangle,
+        vector::Vector,
+    };
+
+    fn flat(face: &Face) -> &Triangle {
+        match face {
+            Face::Flat(triangle) => triangle,
+            Face::Smooth(_) => panic!("expected a flat triangle"),
+        }
+    }
+
+    fn smooth(face: &Face) -> &SmoothTriangle {
+        match face {
+            Face::Smooth(triangle) => triangle,
+            Face::Flat(_) => panic!("expected a smooth triangle"),
+        }
+    }
+
+    #[test]
+    fn ignore_unrecognized_lines() {
+        let obj = ObjFile::parse(
+            "There was a young lady named Bright
+who traveled much faster than light.
+She set out one day
+in a relative way,
+and came back the previous night.",
+        );
+        assert_eq!(obj.ignored, 5);
+        assert!(obj.vertices.is_empty());
+    }
+    #[test]
+    fn ignore_malformed_lines() {
+        let obj = ObjFile::parse(
+            "# comment
+v 1 2
+v 1 two 3
+f 1 2 3
+g",
+        );
+        assert_eq!(obj.ignored, 4);
+    }
+    #[test]
+    fn vertex_records() {
+        let obj = ObjFile::parse(
+            "v -1 1 0
+v -1.0000 0.5000 0.0000
+v 1 0 0
+v 1 1 0",
+        );
+        assert_eq!(
+            obj.vertices,
+            vec![
+                Point::new(-1.0, 1.0, 0.0),
+                Point::new(-1.0, 0.5, 0.0),
+                Point::new(1.0, 0.0, 0.0),
+                Point::new(1.0, 1.0, 0.0),
+            ]
+        );
+        assert_eq!(obj.ignored, 0);
+    }
+    #[test]
+    fn triangle_faces() {
+        let obj = ObjFile::parse(
+            "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+
+f 1 2 3
+f 1 3 4",
+        );
+        assert_eq!(obj.ignored, 0);
+        let faces = &obj.groups[0].faces;
+        assert_eq!(faces.len(), 2);
+        let (t1, t2) = (flat(&faces[0]), flat(&faces[1]));
+        assert_eq!(t1.p1(), obj.vertices[0]);
+        assert_eq!(t1.p2(), obj.vertices[1]);
+        assert_eq!(t1.p3(), obj.vertices[2]);
+        assert_eq!(t2.p1(), obj.vertices[0]);
+        assert_eq!(t2.p2(), obj.vertices[2]);
+        assert_eq!(t2.p3(), obj.vertices[3]);
+    }
+    #[test]
+    fn vertex_extras_are_discarded() {
+        let obj = ObjFile::parse(
+            "v -1 1 0 1
+v -1 0 0 0.5 0.5 0.5
+v 1 0 0
+
+f 1 2 3",
+        );
+        assert_eq!(obj.ignored, 0);
+        assert_eq!(obj.vertices.len(), 3);
+        let triangle = flat(&obj.groups[0].faces[0]);
+        assert_eq!(triangle.p1(), Point::new(-1.0, 1.0, 0.0));
+        assert_eq!(triangle.p2(), Point::new(-1.0, 0.0, 0.0));
+        assert_eq!(triangle.p3(), Point::new(1.0, 0.0, 0.0));
+    }
+    #[test]
+    fn relative_face_indices() {
+        let obj = ObjFile::parse(
+            "v 0 5 0
+v -1 1 0
+v -1 0 0
+v 1 0 0
+vn 0 0 1
+f -3 -2 -1
+f -3//-1 -2//1 1//-1
+f 0 1 2
+f -5 1 2",
+        );
+        assert_eq!(obj.ignored, 2);
+        let faces = &obj.groups[0].faces;
+        assert_eq!(faces.len(), 2);
+        let triangle = flat(&faces[0]);
+        assert_eq!(triangle.p1(), obj.vertices[1]);
+        assert_eq!(triangle.p2(), obj.vertices[2]);
+        assert_eq!(triangle.p3(), obj.vertices[3]);
+        let triangle = smooth(&faces[1]);
+        assert_eq!(triangle.p1(), obj.vertices[1]);
+        assert_eq!(triangle.p3(), obj.vertices[0]);
+        assert_eq!(triangle.n1(), obj.normals[0]);
+    }
+    #[test]
+    fn triangulate_polygons() {
+        let obj = ObjFile::parse(
+            "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+v 0 2 0
+
+f 1 2 3 4 5",
+        );
+        let faces = &obj.groups[0].faces;
+        assert_eq!(faces.len(), 3);
+        for (face, (a, b)) in faces.iter().zip([(1, 2), (2, 3), (3, 4)]) {
+            let triangle = flat(face);
+            assert_eq!(triangle.p1(), obj.vertices[0]);
+            assert_eq!(triangle.p2(), obj.vertices[a]);
+            assert_eq!(triangle.p3(), obj.vertices[b]);
+        }
+    }
+    #[test]
+    fn named_groups() {
+        let obj = ObjFile::parse(
+            "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+
+g FirstGroup
+f 1 2 3
+g SecondGroup
+f 1 3 4",
+        );
+        assert_eq!(obj.groups.len(), 3);
+        assert!(obj.groups[0].faces.is_empty());
+        assert_eq!(obj.groups[1].name.as_deref(), Some("FirstGroup"));
+        assert_eq!(obj.groups[2].name.as_deref(), Some("SecondGroup"));
+        let (t1, t2) = (flat(&obj.groups[1].faces[0]), flat(&obj.groups[2].faces[0]));
+        assert_eq!(t1.p3(), obj.vertices[2]);
+        assert_eq!(t2.p3(), obj.vertices[3]);
+    }
+    #[test]
+    fn convert_to_group() {
+        let obj = ObjFile::parse(
+            "v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+
+f 1 2 3
+g FirstGroup
+f 1 2 3
+g SecondGroup
+f 1 3 4",
+        );
+        let group = obj.into_group();
+        assert_eq!(group.len(), 3);
+        let ray = Ray::new(Point::new(-0.5, 0.5, -2.0), Vector::z_unit());
+        assert_eq!(group.intersect(&ray).len(), 2);
+    }
+    #[test]
+    fn vertex_normal_records() {
+        let obj = ObjFile::parse(
+            "vn 0 0 1
+vn 0.707 0 -0.707
+vn 1 2 3",
+        );
+        assert_eq!(
+            obj.normals,
+            vec![
+                Vector::z_unit(),
+                Vector::new(0.707, 0.0, -0.707),
+                Vector::new(1.0, 2.0, 3.0),
+            ]
+        );
+    }
+    #[test]
+    fn faces_with_normals() {
+        let obj = ObjFile::parse(
+            "v 0 1 0
+v -1 0 0
+v 1 0 0
+
+vn -1 0 0
+vn 1 0 0
+vn 0 1 0
+
+f 1//3 2//1 3//2
+f 1/0/3 2/102/1 3/14/2",
+        );
+        let faces = &obj.groups[0].faces;
+        let (t1, t2) = (smooth(&faces[0]), smooth(&faces[1]));
+        assert_eq!(t1.p1(), obj.vertices[0]);
+        assert_eq!(t1.p2(), obj.vertices[1]);
+        assert_eq!(t1.p3(), obj.vertices[2]);
+        assert_eq!(t1.n1(), obj.normals[2]);
+        assert_eq!(t1.n2(), obj.normals[0]);
+        assert_eq!(t1.n3(), obj.normals[1]);
+        assert_eq!(t1, t2);
+    }
+}
@@ -0,0 +1,357 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use crate::{color::Color, matrix::Matrix4, point::Point, shape::Shape};
+
+/// A color that varies over space. Patterns carry their own transform and are
+/// evaluated in pattern space; the sub-patterns of a composite pattern are
+/// evaluated in their parent's pattern space, so transforms nest.
+///
+/// `Color` is a pattern too, so composites can take flat colors as leaves.
+/// A solid color looks the same everywhere, so it ignores any transform
+/// given to it.
+pub(crate) trait Pattern: Debug {
+    #[allow(dead_code)]
+    fn transform(&self) -> Matrix4;
+
+    /// Cached inverse of `transform`.
+    fn inverse(&self) -> Matrix4;
+
+    /// Has no effect on a `Color`.
+    fn set_transform(&mut self, transform: Matrix4);
+
+    /// Color at a point already converted to pattern space.
+    fn local_pattern_at(&self, point: Point) -> Color;
+
+    fn with_transform(mut self, transform: Matrix4) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_transform(transform);
+        self
+    }
+
+    /// Color at a point given in the space that contains this pattern.
+    fn pattern_at(&self, point: Point) -> Color {
+        self.local_pattern_at(self.inverse() * point)
+    }
+
+    fn pattern_at_shape(&self, shape: &dyn Shape, world_point: Point) -> Color {
+        self.pattern_at(shape.inverse() * world_point)
+    }
+}
+
+/// A plain color is the simplest pattern, and lets composite patterns mix
+/// flat colors with other patterns.
+impl Pattern for Color {
+    fn transform(&self) -> Matrix4 {
+        Matrix4::identity()
+    }
+
+    fn inverse(&self) -> Matrix4 {
+        Matrix4::identity()
+    }
+
+    // Nothing to store: see the note on `Pattern`
+    fn set_transform(&mut self, _transform: Matrix4) {}
+
+    fn local_pattern_at(&self, _point: Point) -> Color {
+        *self
+    }
+}
+
+/// Two sub-patterns plus the pattern's own transform; `M` decides how the
+/// parts are combined at each point.
+#[derive(Debug)]
+pub(crate) struct Composite<M> {
+    a: Box<dyn Pattern>,
+    b: Box<dyn Pattern>,
+    transform: Matrix4,
+    inverse: Matrix4,
+    mix: PhantomData<M>,
+}
+
+/// How a `Composite` combines its two sub-patterns at a point in pattern
+/// space.
+pub(crate) trait Mix: Debug {
+    fn mix(a: &dyn Pattern, b: &dyn Pattern, point: Point) -> Color;
+}
+
+impl<M: Mix> Composite<M> {
+    pub fn new(a: impl Pattern + 'static, b: impl Pattern + 'static) -> Self {
+        Composite {
+            a: Box::new(a),
+            b: Box::new(b),
+            transform: Matrix4::identity(),
+            inverse: Matrix4::identity(),
+            mix: PhantomData,
+        }
+    }
+}
+
+impl<M: Mix> Pattern for Composite<M> {
+    fn transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn inverse(&self) -> Matrix4 {
+        self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix4) {
+        self.inverse = transform
+            .inverse()
+            .expect("pattern transform must be invertible");
+        self.transform = transform;
+    }
+
+    fn local_pattern_at(&self, point: Point) -> Color {
+        M::mix(self.a.as_ref(), self.b.as_ref(), point)
+    }
+}
+
+/// Alternates between `a` and `b` every unit along x.
+pub(crate) type Stripe = Composite<StripeMix>;
+
+/// Blends linearly from `a` to `b` across each unit along x.
+pub(crate) type Gradient = Composite<GradientMix>;
+
+/// Concentric rings around the y axis, alternating every unit of radius.
+pub(crate) type Ring = Composite<RingMix>;
+
+/// Alternating unit cubes in all three dimensions.
+pub(crate) type Checkers = Composite<CheckersMix>;
+
+/// The average of two patterns at every point.
+pub(crate) type Blend = Composite<BlendMix>;
+
+#[derive(Debug)]
+pub(crate) struct StripeMix;
+
+#[derive(Debug)]
+pub(crate) struct GradientMix;
+
+#[derive(Debug)]
+pub(crate) struct RingMix;
+
+#[derive(Debug)]
+pub(crate) struct CheckersMix;
+
+#[derive(Debug)]
+pub(crate) struct BlendMix;
+
+impl Mix for StripeMix {
+    fn mix(a: &dyn Pattern, b: &dyn Pattern, point: Point) -> Color {
+        if point.x.floor().rem_euclid(2.0) == 0.0 {
+            a.pattern_at(point)
+        } else {
+            b.pattern_at(point)
+        }
+    }
+}
+
+impl Mix for GradientMix {
+    fn mix(a: &dyn Pattern, b: &dyn Pattern, point: Point) -> Color {
+        let a = a.pattern_at(point);
+        let b = b.pattern_at(point);
+        a + (b - a) * (point.x - point.x.floor())
+    }
+}
+
+impl Mix for RingMix {
+    fn mix(a: &dyn Pattern, b: &dyn Pattern, point: Point) -> Color {
+        let radius = (point.x * point.x + point.z * point.z).sqrt();
+        if radius.floor().rem_euclid(2.0) == 0.0 {
+            a.pattern_at(point)
+        } else {
+            b.pattern_at(point)
+        }
+    }
+}
+
+impl Mix for CheckersMix {
+    fn mix(a: &dyn Pattern, b: &dyn Pattern, point: Point) -> Color {
+        let sum = point.x.floor() + point.y.floor() + point.z.floor();
+        if sum.rem_euclid(2.0) == 0.0 {
+            a.pattern_at(point)
+        } else {
+            b.pattern_at(point)
+        }
+    }
+}
+
+impl Mix for BlendMix {
+    fn mix(a: &dyn Pattern, b: &dyn Pattern, point: Point) -> Color {
+        (a.pattern_at(point) + b.pattern_at(point)) / 2.0
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        color::{Color, BLACK, WHITE},
+        matrix::Matrix4,
+        pattern::{Blend, Checkers, Gradient, Pattern, Ring, Stripe},
+        point::Point,
+        shape::Shape,
+        sphere::Sphere,
+        transform::{scaling, translation},
+    };
+
+    /// Returns the pattern-space point it was evaluated at as a color.
+    #[derive(Debug)]
+    pub(crate) struct TestPattern {
+        transform: Matrix4,
+        inverse: Matrix4,
+    }
+
+    impl TestPattern {
+        pub fn new() -> Self {
+            TestPattern {
+                transform: Matrix4::identity(),
+                inverse: Matrix4::identity(),
+            }
+        }
+    }
+
+    impl Pattern for TestPattern {
+        fn transform(&self) -> Matrix4 {
+            self.transform
+        }
+
+        fn inverse(&self) -> Matrix4 {
+            self.inverse
+        }
+
+        fn set_transform(&mut self, transform: Matrix4) {
+            self.inverse = transform.inverse().unwrap();
+            self.transform = transform;
+        }
+
+        fn local_pattern_at(&self, point: Point) -> Color {
+            Color::new(point.x, point.y, point.z)
+        }
+    }
+
+    #[test]
+    fn default_pattern_transform() {
+        let pattern = TestPattern::new();
+        assert_eq!(pattern.transform(), Matrix4::identity());
+    }
+    #[test]
+    fn assign_pattern_transform() {
+        let pattern = TestPattern::new().with_transform(translation(1.0, 2.0, 3.0));
+        assert_eq!(pattern.transform(), translation(1.0, 2.0, 3.0));
+    }
+    #[test]
+    fn pattern_with_object_transform() {
+        let shape = Sphere::new().with_transform(scaling(2.0, 2.0, 2.0));
+        let pattern = TestPattern::new();
+        assert_eq!(
+            pattern.pattern_at_shape(&shape, Point::new(2.0, 3.0, 4.0)),
+            Color::new(1.0, 1.5, 2.0)
+        );
+    }
+    #[test]
+    fn pattern_with_pattern_transform() {
+        let shape = Sphere::new();
+        let pattern = TestPattern::new().with_transform(scaling(2.0, 2.0, 2.0));
+        assert_eq!(
+            pattern.pattern_at_shape(&shape, Point::new(2.0, 3.0, 4.0)),
+            Color::new(1.0, 1.5, 2.0)
+        );
+    }
+    #[test]
+    fn pattern_with_object_and_pattern_transform() {
+        let shape = Sphere::new().with_transform(scaling(2.0, 2.0, 2.0));
+        let pattern = TestPattern::new().with_transform(translation(0.5, 1.0, 1.5));
+        assert_eq!(
+            pattern.pattern_at_shape(&shape, Point::new(2.5, 3.0, 3.5)),
+            Color::new(0.75, 0.5, 0.25)
+        );
+    }
+    #[test]
+    fn stripe_is_constant_in_y_and_z() {
+        let pattern = Stripe::new(WHITE, BLACK);
+        assert_eq!(pattern.pattern_at(Point::origin()), WHITE);
+        assert_eq!(pattern.pattern_at(Point::new(0.0, 1.0, 0.0)), WHITE);
+        assert_eq!(pattern.pattern_at(Point::new(0.0, 2.0, 0.0)), WHITE);
+        assert_eq!(pattern.pattern_at(Point::new(0.0, 0.0, 1.0)), WHITE);
+        assert_eq!(pattern.pattern_at(Point::new(0.0, 0.0, 2.0)), WHITE);
+    }
+    #[test]
+    fn stripe_alternates_in_x() {
+        let pattern = Stripe::new(WHITE, BLACK);
+        assert_eq!(pattern.pattern_at(Point::origin()), WHITE);
+        assert_eq!(pattern.pattern_at(Point::new(0.9, 0.0, 0.0)), WHITE);
+        assert_eq!(pattern.pattern_at(Point::new(1.0, 0.0, 0.0)), BLACK);
+        assert_eq!(pattern.pattern_at(Point::new(-0.1, 0.0, 0.0)), BLACK);
+        assert_eq!(pattern.pattern_at(Point::new(-1.0, 0.0, 0.0)), BLACK);
+        assert_eq!(pattern.pattern_at(Point::new(-1.1, 0.0, 0.0)), WHITE);
+    }
+    #[test]
+    fn gradient_interpolates_between_colors() {
+        let pattern = Gradient::new(WHITE, BLACK);
+        assert_eq!(pattern.pattern_at(Point::origin()), WHITE);
+        assert_eq!(
+            pattern.pattern_at(Point::new(0.25, 0.0, 0.0)),
+            Color::new(0.75, 0.75, 0.75)
+        );
+        assert_eq!(
+            pattern.pattern_at(Point::new(0.5, 0.0, 0.0)),
+            Color::new(0.5, 0.5, 0.5)
+        );
+        assert_eq!(
+            pattern.pattern_at(Point::new(0.75, 0.0, 0.0)),
+            Color::new(0.25, 0.25, 0.25)
+        );
+    }
+    #[test]
+    fn ring_extends_in_x_and_z() {
+        let pattern = Ring::new(WHITE, BLACK);
+        assert_eq!(pattern.pattern_at(Point::origin()), WHITE);
+        assert_eq!(pattern.pattern_at(Point::new(1.0, 0.0, 0.0)), BLACK);
+        assert_eq!(pattern.pattern_at(Point::new(0.0, 0.0, 1.0)), BLACK);
+        // 0.708 is just past sqrt(2) / 2
+        assert_eq!(pattern.pattern_at(Point::new(0.708, 0.0, 0.708)), BLACK);
+    }
+    #[test]
+    fn checkers_repeat_in_x() {
+        let pattern = Checkers::new(WHITE, BLACK);
+        assert_eq!(pattern.pattern_at(Point::origin()), WHITE);
+        assert_eq!(pattern.pattern_at(Point::new(0.99, 0.0, 0.0)), WHITE);
+        assert_eq!(pattern.pattern_at(Point::new(1.01, 0.0, 0.0)), BLACK);
+    }
+    #[test]
+    fn checkers_repeat_in_y() {
+        let pattern = Checkers::new(WHITE, BLACK);
+        assert_eq!(pattern.pattern_at(Point::new(0.0, 0.99, 0.0)), WHITE);
+        assert_eq!(pattern.pattern_at(Point::new(0.0, 1.01, 0.0)), BLACK);
+    }
+    #[test]
+    fn checkers_repeat_in_z() {
+        let pattern = Checkers::new(WHITE, BLACK);
+        assert_eq!(pattern.pattern_at(Point::new(0.0, 0.0, 0.99)), WHITE);
+        assert_eq!(pattern.pattern_at(Point::new(0.0, 0.0, 1.01)), BLACK);
+    }
+    #[test]
+    fn nested_patterns_use_their_own_transform() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let inner = Stripe::new(red, BLACK).with_transform(scaling(0.5, 1.0, 1.0));
+        let pattern = Checkers::new(inner, WHITE);
+        assert_eq!(pattern.pattern_at(Point::new(0.25, 0.0, 0.0)), red);
+        assert_eq!(pattern.pattern_at(Point::new(0.75, 0.0, 0.0)), BLACK);
+        assert_eq!(pattern.pattern_at(Point::new(1.25, 0.0, 0.0)), WHITE);
+    }
+    #[test]
+    fn blend_averages_patterns() {
+        let pattern = Blend::new(Stripe::new(WHITE, BLACK), Gradient::new(BLACK, WHITE));
+        assert_eq!(
+            pattern.pattern_at(Point::origin()),
+            Color::new(0.5, 0.5, 0.5)
+        );
+        assert_eq!(
+            pattern.pattern_at(Point::new(1.5, 0.0, 0.0)),
+            Color::new(0.25, 0.25, 0.25)
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    intersection::Intersection, material::Material, matrix::Matrix4, point::Point, ray::Ray,
+    shape::Shape, vector::Vector, EPSILON,
+};
+
+/// Infinite plane through the origin, spanning x and z.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Plane {
+    transform: Matrix4,
+    inverse: Matrix4,
+    material: Material,
+}
+
+impl Plane {
+    pub fn new() -> Self {
+        Plane {
+            transform: Matrix4::identity(),
+            inverse: Matrix4::identity(),
+            material: Material::default(),
+        }
+    }
+}
+
+impl Default for Plane {
+    fn default() -> Self {
+        Plane::new()
+    }
+}
+
+impl Shape for Plane {
+    fn transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn inverse(&self) -> Matrix4 {
+        self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix4) {
+        self.inverse = transform
+            .inverse()
+            .expect("plane transform must be invertible");
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        // Parallel (or coplanar) rays never cross the plane
+        if ray.direction.y.abs() < EPSILON {
+            return vec![];
+        }
+        vec![Intersection::new(-ray.origin.y / ray.direction.y, self)]
+    }
+
+    fn local_normal_at(&self, _point: Point, _hit: &Intersection) -> Vector {
+        Vector::y_unit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        intersection::Intersection, plane::Plane, point::Point, ray::Ray, shape::Shape,
+        vector::Vector,
+    };
+
+    #[test]
+    fn normal_is_constant_everywhere() {
+        let plane = Plane::new();
+        assert_eq!(
+            plane.local_normal_at(Point::origin(), &Intersection::new(0.0, &plane)),
+            Vector::y_unit()
+        );
+        assert_eq!(
+            plane.local_normal_at(
+                Point::new(10.0, 0.0, -10.0),
+                &Intersection::new(0.0, &plane)
+            ),
+            Vector::y_unit()
+        );
+        assert_eq!(
+            plane.local_normal_at(
+                Point::new(-5.0, 0.0, 150.0),
+                &Intersection::new(0.0, &plane)
+            ),
+            Vector::y_unit()
+        );
+    }
+    #[test]
+    fn intersect_with_parallel_ray() {
+        let plane = Plane::new();
+        let ray = Ray::new(Point::new(0.0, 10.0, 0.0), Vector::z_unit());
+        assert!(plane.local_intersect(&ray).is_empty());
+    }
+    #[test]
+    fn intersect_with_coplanar_ray() {
+        let plane = Plane::new();
+        let ray = Ray::new(Point::origin(), Vector::z_unit());
+        assert!(plane.local_intersect(&ray).is_empty());
+    }
+    #[test]
+    fn intersect_from_above() {
+        let plane = Plane::new();
+        let ray = Ray::new(Point::new(0.0, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        let intersections = plane.local_intersect(&ray);
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections[0].t, 1.0);
+        assert!(std::ptr::addr_eq(intersections[0].object, &plane));
+    }
+    #[test]
+    fn intersect_from_below() {
+        let plane = Plane::new();
+        let ray = Ray::new(Point::new(0.0, -1.0, 0.0), Vector::y_unit());
+        let intersections = plane.local_intersect(&ray);
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections[0].t, 1.0);
+        assert!(std::ptr::addr_eq(intersections[0].object, &plane));
+    }
+}
@@ -0,0 +1,172 @@
+use std::io::Write;
+
+use crate::canvas::Canvas;
+
+const SIGNATURE: &[u8; 8] = b"\x89PNG\r\n\x1a\n";
+
+/// Largest payload a stored (uncompressed) deflate block can hold.
+const MAX_STORED_BLOCK: usize = 65535;
+
+impl Canvas {
+    /// Writes the canvas as an 8-bit RGB PNG. Image data goes into zlib
+    /// stored blocks, so files are roughly raw size, but nothing outside the
+    /// standard library is needed.
+    pub fn write_png<W: Write>(&self, mut w: W) -> std::io::Result<()> {
+        w.write_all(SIGNATURE)?;
+
+        let mut header = vec![];
+        header.extend((self.width() as u32).to_be_bytes());
+        header.extend((self.height() as u32).to_be_bytes());
+        // Bit depth 8, truecolor, deflate, adaptive filtering, no interlace
+        header.extend([8, 2, 0, 0, 0]);
+        write_chunk(&mut w, b"IHDR", &header)?;
+
+        let pixels: Vec<[u8; 3]> = self.rgb8_pixels().collect();
+        let mut raw = Vec::with_capacity((self.width() * 3 + 1) * self.height());
+        for row in pixels.chunks(self.width().max(1)) {
+            // Filter type 0: the row is stored as-is
+            raw.push(0);
+            raw.extend(row.iter().flatten());
+        }
+        write_chunk(&mut w, b"IDAT", &zlib_stored(&raw))?;
+        write_chunk(&mut w, b"IEND", &[])?;
+        w.flush()
+    }
+}
+
+fn write_chunk<W: Write>(w: &mut W, kind: &[u8; 4], data: &[u8]) -> std::io::Result<()> {
+    w.write_all(&(data.len() as u32).to_be_bytes())?;
+    w.write_all(kind)?;
+    w.write_all(data)?;
+    let crc = crc32(kind.iter().chain(data));
+    w.write_all(&crc.to_be_bytes())
+}
+
+/// Wraps `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // Deflate with a 32K window, no preset dictionary; 0x78 0x01 passes the
+    // header checksum
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_STORED_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        // Even empty input needs one final block
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    let crc = bytes.into_iter().fold(0xffff_ffff_u32, |mut crc, &byte| {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+        crc
+    });
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    const MOD_ADLER: u32 = 65521;
+    let (a, b) = bytes.iter().fold((1_u32, 0_u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % MOD_ADLER;
+        (a, (b + a) % MOD_ADLER)
+    });
+    b << 16 | a
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        canvas::Canvas,
+        color::{Color, WHITE},
+        png::{adler32, crc32, zlib_stored, MAX_STORED_BLOCK},
+    };
+
+    /// Splits a PNG into `(kind, data)` chunks, checking each CRC.
+    fn chunks(png: &[u8]) -> Vec<([u8; 4], Vec<u8>)> {
+        let mut chunks = vec![];
+        let mut rest = &png[8..];
+        while !rest.is_empty() {
+            let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let kind: [u8; 4] = rest[4..8].try_into().unwrap();
+            let data = rest[8..8 + len].to_vec();
+            let crc = u32::from_be_bytes(rest[8 + len..12 + len].try_into().unwrap());
+            assert_eq!(crc, crc32(kind.iter().chain(&data)));
+            chunks.push((kind, data));
+            rest = &rest[12 + len..];
+        }
+        chunks
+    }
+
+    /// Reassembles the payload of a zlib stream made of stored blocks.
+    fn inflate_stored(stream: &[u8]) -> Vec<u8> {
+        assert_eq!(u16::from_be_bytes([stream[0], stream[1]]) % 31, 0);
+        let mut data = vec![];
+        let mut pos = 2;
+        loop {
+            let last = stream[pos] & 1 == 1;
+            assert_eq!(stream[pos] >> 1, 0, "expected a stored block");
+            let len = u16::from_le_bytes([stream[pos + 1], stream[pos + 2]]);
+            let nlen = u16::from_le_bytes([stream[pos + 3], stream[pos + 4]]);
+            assert_eq!(len, !nlen);
+            data.extend(&stream[pos + 5..pos + 5 + len as usize]);
+            pos += 5 + len as usize;
+            if last {
+                break;
+            }
+        }
+        let checksum = u32::from_be_bytes(stream[pos..pos + 4].try_into().unwrap());
+        assert_eq!(checksum, adler32(&data));
+        assert_eq!(pos + 4, stream.len());
+        data
+    }
+
+    #[test]
+    fn crc32_of_known_input() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+    #[test]
+    fn adler32_of_known_input() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+    #[test]
+    fn zlib_splits_large_input_into_blocks() {
+        let data: Vec<u8> = (0..MAX_STORED_BLOCK * 2 + 10).map(|i| i as u8).collect();
+        let stream = zlib_stored(&data);
+        assert_eq!(stream.len(), 2 + 3 * 5 + data.len() + 4);
+        assert_eq!(inflate_stored(&stream), data);
+        assert_eq!(inflate_stored(&zlib_stored(&[])), Vec::<u8>::new());
+    }
+    #[test]
+    fn png_layout() {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.write_pixel(0, 0, Color::new(1.0, 0.0, 0.0));
+        canvas.write_pixel(1, 1, WHITE);
+        let mut png = vec![];
+        canvas.write_png(&mut png).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        let chunks = chunks(&png);
+        let kinds: Vec<&[u8; 4]> = chunks.iter().map(|(kind, _)| kind).collect();
+        assert_eq!(kinds, vec![b"IHDR", b"IDAT", b"IEND"]);
+        assert_eq!(chunks[0].1, [0, 0, 0, 2, 0, 0, 0, 2, 8, 2, 0, 0, 0]);
+        assert_eq!(
+            inflate_stored(&chunks[1].1),
+            [0, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 255, 255, 255]
+        );
+        assert!(chunks[2].1.is_empty());
+    }
+}
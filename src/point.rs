@@ -1,8 +1,8 @@
 use crate::{tuple::Tuple, vector::Vector};
-use float_cmp::approx_eq;
+use float_cmp::{approx_eq, ApproxEq, F32Margin};
 use std::fmt;
 
-#[derive(Debug)]
+#[derive(Copy, Clone, Debug)]
 pub(crate) struct Point {
     pub x: f32,
     pub y: f32,
@@ -49,6 +49,17 @@ impl PartialEq for Point {
     }
 }
 
+impl ApproxEq for Point {
+    type Margin = F32Margin;
+
+    fn approx_eq<M: Into<Self::Margin>>(self, other: Self, margin: M) -> bool {
+        let margin = margin.into();
+        self.x.approx_eq(other.x, margin)
+            && self.y.approx_eq(other.y, margin)
+            && self.z.approx_eq(other.z, margin)
+    }
+}
+
 impl std::ops::Add<Vector> for Point {
     type Output = Point;
 
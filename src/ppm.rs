@@ -0,0 +1,401 @@
+use std::{fmt, io::Read};
+
+use crate::{canvas::Canvas, color::Color};
+
+/// Why a PPM image couldn't be loaded.
+#[derive(Debug)]
+pub(crate) enum PpmError {
+    Io(std::io::Error),
+    /// The file doesn't start with `P3` or `P6`
+    BadMagic,
+    /// A header field is missing or not a valid number
+    BadHeader(&'static str),
+    /// A P3 sample isn't a number, or a sample exceeds the max value
+    BadSample(String),
+    /// The file ended before every sample was read
+    Truncated {
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for PpmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PpmError::Io(err) => write!(f, "failed to read PPM: {err}"),
+            PpmError::BadMagic => write!(f, "not a P3 or P6 PPM file"),
+            PpmError::BadHeader(field) => write!(f, "invalid PPM header: bad {field}"),
+            PpmError::BadSample(sample) => write!(f, "invalid PPM sample `{sample}`"),
+            PpmError::Truncated { expected, found } => write!(
+                f,
+                "truncated PPM pixel data: expected {expected} samples, found {found}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PpmError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PpmError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for PpmError {
+    fn from(err: std::io::Error) -> Self {
+        PpmError::Io(err)
+    }
+}
+
+/// Walks the header, skipping whitespace and `#` comments between tokens.
+struct Tokens<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn next_token(&mut self) -> Option<&'a [u8]> {
+        loop {
+            match self.bytes.get(self.pos)? {
+                b'#' => {
+                    while self.bytes.get(self.pos).is_some_and(|&b| b != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                b if b.is_ascii_whitespace() => self.pos += 1,
+                _ => break,
+            }
+        }
+        let start = self.pos;
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(|b| !b.is_ascii_whitespace() && *b != b'#')
+        {
+            self.pos += 1;
+        }
+        Some(&self.bytes[start..self.pos])
+    }
+
+    fn next_number(&mut self, field: &'static str) -> Result<u32, PpmError> {
+        self.next_token()
+            .and_then(|token| std::str::from_utf8(token).ok())
+            .and_then(|token| token.parse().ok())
+            .ok_or(PpmError::BadHeader(field))
+    }
+}
+
+impl Canvas {
+    /// Loads a plain (P3) or binary (P6) PPM, rescaling samples by the
+    /// file's max value into 0.0..=1.0 and decoding them from sRGB to linear
+    /// light.
+    #[allow(dead_code)]
+    pub fn read_ppm<R: Read>(mut r: R) -> Result<Canvas, PpmError> {
+        let mut bytes = vec![];
+        r.read_to_end(&mut bytes)?;
+        let mut tokens = Tokens {
+            bytes: &bytes,
+            pos: 0,
+        };
+        let binary = match tokens.next_token() {
+            Some(b"P3") => false,
+            Some(b"P6") => true,
+            _ => return Err(PpmError::BadMagic),
+        };
+        let width = tokens.next_number("width")? as usize;
+        let height = tokens.next_number("height")? as usize;
+        let max_value = tokens.next_number("max value")?;
+        if !(1..=65535).contains(&max_value) {
+            return Err(PpmError::BadHeader("max value"));
+        }
+
+        let expected = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(3))
+            .ok_or(PpmError::BadHeader("dimensions"))?;
+        let samples = if binary {
+            // Exactly one whitespace byte separates the header from the raster
+            let raster = bytes.get(tokens.pos + 1..).unwrap_or_default();
+            read_binary_samples(raster, expected, max_value)?
+        } else {
+            read_plain_samples(&mut tokens, expected)?
+        };
+        if let Some(sample) = samples.iter().find(|&&sample| sample > max_value) {
+            return Err(PpmError::BadSample(sample.to_string()));
+        }
+
+        let mut canvas = Canvas::new(width, height);
+        let max_value = max_value as f32;
+        for (index, rgb) in samples.chunks(3).enumerate() {
+            let color = Color::new(
+                rgb[0] as f32 / max_value,
+                rgb[1] as f32 / max_value,
+                rgb[2] as f32 / max_value,
+            )
+            .decode_srgb();
+            canvas.write_pixel(index % width, index / width, color);
+        }
+        Ok(canvas)
+    }
+}
+
+fn read_plain_samples(tokens: &mut Tokens, expected: usize) -> Result<Vec<u32>, PpmError> {
+    // The header's dimensions are untrusted, so let the vector grow with the
+    // samples actually present rather than reserving for the claimed count
+    let mut samples = Vec::new();
+    while samples.len() < expected {
+        let Some(token) = tokens.next_token() else {
+            return Err(PpmError::Truncated {
+                expected,
+                found: samples.len(),
+            });
+        };
+        let token = String::from_utf8_lossy(token);
+        samples.push(
+            token
+                .parse()
+                .map_err(|_| PpmError::BadSample(token.into_owned()))?,
+        );
+    }
+    Ok(samples)
+}
+
+/// Samples are one byte each, or two big-endian bytes when the max value
+/// exceeds 255.
+fn read_binary_samples(
+    raster: &[u8],
+    expected: usize,
+    max_value: u32,
+) -> Result<Vec<u32>, PpmError> {
+    let sample_size = if max_value > 255 { 2 } else { 1 };
+    let found = raster.len() / sample_size;
+    if found < expected {
+        return Err(PpmError::Truncated { expected, found });
+    }
+    Ok(raster
+        .chunks_exact(sample_size)
+        .take(expected)
+        .map(|sample| {
+            sample
+                .iter()
+                .fold(0, |value, &byte| value << 8 | byte as u32)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+
+    use crate::{
+        canvas::Canvas,
+        color::{Color, BLACK, WHITE},
+        ppm::PpmError,
+    };
+
+    #[test]
+    fn reject_wrong_magic_number() {
+        let ppm = "P32\n1 1\n255\n0 0 0\n";
+        assert!(matches!(
+            Canvas::read_ppm(ppm.as_bytes()),
+            Err(PpmError::BadMagic)
+        ));
+    }
+    #[test]
+    fn read_dimensions() {
+        let ppm = "P3\n10 2\n255\n".to_string() + &"0 0 0\n".repeat(20);
+        let canvas = Canvas::read_ppm(ppm.as_bytes()).unwrap();
+        assert_eq!(canvas.width(), 10);
+        assert_eq!(canvas.height(), 2);
+    }
+    #[test]
+    fn read_pixel_data() {
+        let ppm = "P3
+4 3
+255
+255 127 0  0 127 255  127 255 0  255 255 255
+0 0 0  255 0 0  0 255 0  0 0 255
+255 255 0  0 255 255  255 0 255  127 127 127
+";
+        let canvas = Canvas::read_ppm(ppm.as_bytes()).unwrap();
+        let half = 127.0 / 255.0;
+        let cases = [
+            (0, 0, Color::new(1.0, half, 0.0)),
+            (1, 0, Color::new(0.0, half, 1.0)),
+            (2, 0, Color::new(half, 1.0, 0.0)),
+            (3, 0, WHITE),
+            (0, 1, BLACK),
+            (1, 1, Color::new(1.0, 0.0, 0.0)),
+            (2, 1, Color::new(0.0, 1.0, 0.0)),
+            (3, 1, Color::new(0.0, 0.0, 1.0)),
+            (0, 2, Color::new(1.0, 1.0, 0.0)),
+            (1, 2, Color::new(0.0, 1.0, 1.0)),
+            (2, 2, Color::new(1.0, 0.0, 1.0)),
+            (3, 2, Color::new(half, half, half)),
+        ];
+        for (x, y, color) in cases {
+            assert_eq!(canvas.pixel_at(x, y), color.decode_srgb());
+        }
+    }
+    #[test]
+    fn ignore_comment_lines() {
+        let ppm = "P3
+# this is a comment
+2 1
+# this, too
+255
+# another comment
+255 255 255
+# oh, no, comments in the pixel data!
+255 0 255
+";
+        let canvas = Canvas::read_ppm(ppm.as_bytes()).unwrap();
+        assert_eq!(canvas.pixel_at(0, 0), WHITE);
+        assert_eq!(canvas.pixel_at(1, 0), Color::new(1.0, 0.0, 1.0));
+    }
+    #[test]
+    fn allow_rgb_triple_to_span_lines() {
+        let ppm = "P3\n1 1\n255\n51\n153\n\n204\n";
+        let canvas = Canvas::read_ppm(ppm.as_bytes()).unwrap();
+        assert_eq!(
+            canvas.pixel_at(0, 0),
+            Color::new(0.2, 0.6, 0.8).decode_srgb()
+        );
+    }
+    #[test]
+    fn rescale_other_max_values() {
+        let ppm = "P3\n2 2\n100\n100 100 100  50 50 50\n75 50 25  0 0 0\n";
+        let canvas = Canvas::read_ppm(ppm.as_bytes()).unwrap();
+        assert!(approx_eq!(
+            Color,
+            canvas.pixel_at(0, 1),
+            Color::new(0.75, 0.5, 0.25).decode_srgb(),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn read_binary_pixel_data() {
+        let mut ppm = b"P6\n# binary\n2 1\n255\n".to_vec();
+        ppm.extend([255, 0, 51, 0, 255, 255]);
+        let canvas = Canvas::read_ppm(ppm.as_slice()).unwrap();
+        assert_eq!(
+            canvas.pixel_at(0, 0),
+            Color::new(1.0, 0.0, 0.2).decode_srgb()
+        );
+        assert_eq!(canvas.pixel_at(1, 0), Color::new(0.0, 1.0, 1.0));
+    }
+    #[test]
+    fn read_sixteen_bit_binary_pixel_data() {
+        let mut ppm = b"P6 1 1 65535\n".to_vec();
+        ppm.extend([0xff, 0xff, 0x00, 0x00, 0x80, 0x00]);
+        let canvas = Canvas::read_ppm(ppm.as_slice()).unwrap();
+        assert!(approx_eq!(
+            Color,
+            canvas.pixel_at(0, 0),
+            Color::new(1.0, 0.0, 0.5).decode_srgb(),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn samples_are_decoded_to_linear_light() {
+        let ppm = "P3\n1 1\n255\n188 0 255\n";
+        let canvas = Canvas::read_ppm(ppm.as_bytes()).unwrap();
+        assert!(approx_eq!(
+            Color,
+            canvas.pixel_at(0, 0),
+            Color::new(0.5, 0.0, 1.0),
+            epsilon = 0.005
+        ));
+    }
+    #[test]
+    fn round_trip_written_ppm() {
+        let mut canvas = Canvas::new(3, 2);
+        canvas.write_pixel(1, 0, WHITE);
+        canvas.write_pixel(2, 1, Color::new(1.0, 0.0, 1.0));
+        let read = Canvas::read_ppm(canvas.to_ppm_string().as_bytes()).unwrap();
+        for y in 0..2 {
+            for x in 0..3 {
+                assert_eq!(read.pixel_at(x, y), canvas.pixel_at(x, y));
+            }
+        }
+    }
+    #[test]
+    fn reject_bad_header() {
+        let ppm = "P3\n2 x\n255\n";
+        assert!(matches!(
+            Canvas::read_ppm(ppm.as_bytes()),
+            Err(PpmError::BadHeader("height"))
+        ));
+        let ppm = "P3\n2 2\n0\n";
+        assert!(matches!(
+            Canvas::read_ppm(ppm.as_bytes()),
+            Err(PpmError::BadHeader("max value"))
+        ));
+    }
+    #[test]
+    fn reject_truncated_pixel_data() {
+        let ppm = "P3\n2 1\n255\n0 0 0 255\n";
+        assert!(matches!(
+            Canvas::read_ppm(ppm.as_bytes()),
+            Err(PpmError::Truncated {
+                expected: 6,
+                found: 4
+            })
+        ));
+        assert!(matches!(
+            Canvas::read_ppm(b"P6 2 1 255".as_slice()),
+            Err(PpmError::Truncated {
+                expected: 6,
+                found: 0
+            })
+        ));
+        let mut ppm = b"P6\n2 1\n255\n".to_vec();
+        ppm.extend([1, 2, 3]);
+        assert!(matches!(
+            Canvas::read_ppm(ppm.as_slice()),
+            Err(PpmError::Truncated {
+                expected: 6,
+                found: 3
+            })
+        ));
+    }
+    #[test]
+    fn reject_huge_dimensions() {
+        let ppm = "P3\n4294967295 4294967295\n255\n";
+        assert!(matches!(
+            Canvas::read_ppm(ppm.as_bytes()),
+            Err(PpmError::BadHeader("dimensions"))
+        ));
+        let ppm = "P3\n100000 100000\n255\n";
+        assert!(matches!(
+            Canvas::read_ppm(ppm.as_bytes()),
+            Err(PpmError::Truncated {
+                expected: 30_000_000_000,
+                found: 0
+            })
+        ));
+        let ppm = "P6\n100000 100000\n255\n";
+        assert!(matches!(
+            Canvas::read_ppm(ppm.as_bytes()),
+            Err(PpmError::Truncated {
+                expected: 30_000_000_000,
+                found: 0
+            })
+        ));
+    }
+    #[test]
+    fn reject_bad_samples() {
+        let ppm = "P3\n1 1\n255\n0 256 0\n";
+        assert!(matches!(
+            Canvas::read_ppm(ppm.as_bytes()),
+            Err(PpmError::BadSample(_))
+        ));
+        let ppm = "P3\n1 1\n255\n0 red 0\n";
+        assert!(matches!(
+            Canvas::read_ppm(ppm.as_bytes()),
+            Err(PpmError::BadSample(_))
+        ));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{
+    intersection::Intersections, matrix::Matrix4, point::Point, shape::Shape, vector::Vector,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct Ray {
+    pub origin: Point,
+    pub direction: Vector,
+}
+
+impl Ray {
+    pub fn new(origin: Point, direction: Vector) -> Self {
+        Ray { origin, direction }
+    }
+
+    pub fn position(&self, t: f32) -> Point {
+        self.origin + self.direction * t
+    }
+
+    pub fn transform(&self, matrix: &Matrix4) -> Self {
+        Ray::new(*matrix * self.origin, *matrix * self.direction)
+    }
+
+    #[allow(dead_code)]
+    pub fn intersect<'a>(&self, shape: &'a dyn Shape) -> Intersections<'a> {
+        shape.intersect(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        point::Point,
+        ray::Ray,
+        shape::Shape,
+        sphere::Sphere,
+        transform::{scaling, translation},
+        vector::Vector,
+    };
+
+    #[test]
+    fn create_ray() {
+        let origin = Point::new(1.0, 2.0, 3.0);
+        let direction = Vector::new(4.0, 5.0, 6.0);
+        let ray = Ray::new(origin, direction);
+        assert_eq!(ray.origin, origin);
+        assert_eq!(ray.direction, direction);
+    }
+    #[test]
+    fn ray_position_at_t() {
+        let ray = Ray::new(Point::new(2.0, 3.0, 4.0), Vector::x_unit());
+        assert_eq!(ray.position(0.0), Point::new(2.0, 3.0, 4.0));
+        assert_eq!(ray.position(1.0), Point::new(3.0, 3.0, 4.0));
+        assert_eq!(ray.position(-1.0), Point::new(1.0, 3.0, 4.0));
+        assert_eq!(ray.position(2.5), Point::new(4.5, 3.0, 4.0));
+    }
+    #[test]
+    fn translate_ray() {
+        let ray = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::y_unit());
+        let new_ray = ray.transform(&translation(3.0, 4.0, 5.0));
+        assert_eq!(new_ray.origin, Point::new(4.0, 6.0, 8.0));
+        assert_eq!(new_ray.direction, Vector::y_unit());
+    }
+    #[test]
+    fn scale_ray() {
+        let ray = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::y_unit());
+        let new_ray = ray.transform(&scaling(2.0, 3.0, 4.0));
+        assert_eq!(new_ray.origin, Point::new(2.0, 6.0, 12.0));
+        assert_eq!(new_ray.direction, Vector::new(0.0, 3.0, 0.0));
+    }
+    #[test]
+    fn transform_leaves_original_ray_unchanged() {
+        let ray = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::y_unit());
+        let _ = ray.transform(&translation(3.0, 4.0, 5.0));
+        assert_eq!(ray.origin, Point::new(1.0, 2.0, 3.0));
+    }
+    #[test]
+    fn ray_intersects_sphere_at_two_points() {
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let sphere = Sphere::new();
+        let intersections = ray.intersect(&sphere);
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0].t, 4.0);
+        assert_eq!(intersections[1].t, 6.0);
+    }
+    #[test]
+    fn ray_intersects_sphere_at_tangent() {
+        let ray = Ray::new(Point::new(0.0, 1.0, -5.0), Vector::z_unit());
+        let sphere = Sphere::new();
+        let intersections = ray.intersect(&sphere);
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0].t, 5.0);
+        assert_eq!(intersections[1].t, 5.0);
+    }
+    #[test]
+    fn ray_misses_sphere() {
+        let ray = Ray::new(Point::new(0.0, 2.0, -5.0), Vector::z_unit());
+        let sphere = Sphere::new();
+        let intersections = ray.intersect(&sphere);
+        assert!(intersections.is_empty());
+        assert_eq!(intersections.hit(), None);
+    }
+    #[test]
+    fn ray_originates_inside_sphere() {
+        let ray = Ray::new(Point::origin(), Vector::z_unit());
+        let sphere = Sphere::new();
+        let intersections = ray.intersect(&sphere);
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0].t, -1.0);
+        assert_eq!(intersections[1].t, 1.0);
+        assert_eq!(intersections.hit().unwrap().t, 1.0);
+    }
+    #[test]
+    fn sphere_behind_ray() {
+        let ray = Ray::new(Point::new(0.0, 0.0, 5.0), Vector::z_unit());
+        let sphere = Sphere::new();
+        let intersections = ray.intersect(&sphere);
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0].t, -6.0);
+        assert_eq!(intersections[1].t, -4.0);
+        assert_eq!(intersections.hit(), None);
+    }
+    #[test]
+    fn intersect_sets_object() {
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let sphere = Sphere::new();
+        let intersections = ray.intersect(&sphere);
+        assert!(std::ptr::addr_eq(intersections[0].object, &sphere));
+        assert!(std::ptr::addr_eq(intersections[1].object, &sphere));
+    }
+    #[test]
+    fn intersect_scaled_sphere() {
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let sphere = Sphere::new().with_transform(scaling(2.0, 2.0, 2.0));
+        let intersections = ray.intersect(&sphere);
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0].t, 3.0);
+        assert_eq!(intersections[1].t, 7.0);
+    }
+    #[test]
+    fn intersect_translated_sphere() {
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let sphere = Sphere::new().with_transform(translation(5.0, 0.0, 0.0));
+        let intersections = ray.intersect(&sphere);
+        assert!(intersections.is_empty());
+    }
+}
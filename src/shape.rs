@@ -0,0 +1,235 @@
+use std::fmt::Debug;
+
+use crate::{
+    intersection::{Intersection, Intersections},
+    material::Material,
+    matrix::Matrix4,
+    point::Point,
+    ray::Ray,
+    vector::Vector,
+};
+
+/// A primitive that can be placed in a world. Implementors only provide the
+/// object-space math (`local_intersect`, `local_normal_at`) plus access to
+/// their transform and material; conversion between world and object space
+/// is handled here.
+pub(crate) trait Shape: Debug {
+    fn transform(&self) -> Matrix4;
+
+    /// Cached inverse of `transform`.
+    fn inverse(&self) -> Matrix4;
+
+    fn set_transform(&mut self, transform: Matrix4);
+
+    fn material(&self) -> &Material;
+
+    fn material_mut(&mut self) -> &mut Material;
+
+    /// Intersections with a ray already converted to object space.
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>>;
+
+    /// Surface normal at a point already converted to object space. `hit` is
+    /// the intersection being shaded, for shapes that interpolate across it.
+    fn local_normal_at(&self, point: Point, hit: &Intersection) -> Vector;
+
+    fn set_material(&mut self, material: Material) {
+        *self.material_mut() = material;
+    }
+
+    fn with_transform(mut self, transform: Matrix4) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_transform(transform);
+        self
+    }
+
+    fn with_material(mut self, material: Material) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_material(material);
+        self
+    }
+
+    /// Whether `other` is this shape or, for composite shapes, one of its
+    /// descendants.
+    fn includes(&self, other: &dyn Shape) -> bool {
+        std::ptr::addr_eq(self, other)
+    }
+
+    fn intersect(&self, ray: &Ray) -> Intersections<'_> {
+        let local_ray = ray.transform(&self.inverse());
+        Intersections::new(self.local_intersect(&local_ray))
+    }
+
+    fn normal_at(&self, world_point: Point, hit: &Intersection) -> Vector {
+        self.normal_to_world(self.local_normal_at(self.world_to_object(world_point), hit))
+    }
+
+    fn world_to_object(&self, point: Point) -> Point {
+        self.inverse() * point
+    }
+
+    fn normal_to_world(&self, normal: Vector) -> Vector {
+        // The inverse transpose keeps normals perpendicular under non-uniform scaling
+        (self.inverse().transpose() * normal).normalize()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{
+        cell::RefCell,
+        f32::consts::{FRAC_1_SQRT_2, PI},
+    };
+
+    use float_cmp::approx_eq;
+
+    use crate::{
+        intersection::Intersection,
+        material::Material,
+        matrix::Matrix4,
+        point::Point,
+        ray::Ray,
+        shape::Shape,
+        transform::{rotation_z, scaling, translation},
+        vector::Vector,
+    };
+
+    /// Records the object-space ray it was last intersected with.
+    #[derive(Debug)]
+    pub(crate) struct TestShape {
+        transform: Matrix4,
+        inverse: Matrix4,
+        material: Material,
+        pub saved_ray: RefCell<Option<Ray>>,
+    }
+
+    impl TestShape {
+        pub fn new() -> Self {
+            TestShape {
+                transform: Matrix4::identity(),
+                inverse: Matrix4::identity(),
+                material: Material::default(),
+                saved_ray: RefCell::new(None),
+            }
+        }
+    }
+
+    impl Shape for TestShape {
+        fn transform(&self) -> Matrix4 {
+            self.transform
+        }
+
+        fn inverse(&self) -> Matrix4 {
+            self.inverse
+        }
+
+        fn set_transform(&mut self, transform: Matrix4) {
+            self.inverse = transform.inverse().expect("transform must be invertible");
+            self.transform = transform;
+        }
+
+        fn material(&self) -> &Material {
+            &self.material
+        }
+
+        fn material_mut(&mut self) -> &mut Material {
+            &mut self.material
+        }
+
+        fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+            self.saved_ray.replace(Some(*ray));
+            vec![]
+        }
+
+        fn local_normal_at(&self, point: Point, _hit: &Intersection) -> Vector {
+            point - Point::origin()
+        }
+    }
+
+    #[test]
+    fn shape_includes_only_itself() {
+        let shape = TestShape::new();
+        let other = TestShape::new();
+        assert!(shape.includes(&shape));
+        assert!(!shape.includes(&other));
+    }
+    #[test]
+    fn default_transform() {
+        let shape = TestShape::new();
+        assert_eq!(shape.transform(), Matrix4::identity());
+    }
+    #[test]
+    fn assign_transform() {
+        let shape = TestShape::new().with_transform(translation(2.0, 3.0, 4.0));
+        assert_eq!(shape.transform(), translation(2.0, 3.0, 4.0));
+        assert_eq!(shape.inverse(), translation(-2.0, -3.0, -4.0));
+    }
+    #[test]
+    fn default_material() {
+        let shape = TestShape::new();
+        assert_eq!(*shape.material(), Material::default());
+    }
+    #[test]
+    fn assign_material() {
+        let material = Material {
+            ambient: 1.0,
+            ..Material::default()
+        };
+        let shape = TestShape::new().with_material(material.clone());
+        assert_eq!(*shape.material(), material);
+    }
+    #[test]
+    fn intersect_scaled_shape() {
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let shape = TestShape::new().with_transform(scaling(2.0, 2.0, 2.0));
+        let _ = shape.intersect(&ray);
+        assert_eq!(
+            *shape.saved_ray.borrow(),
+            Some(Ray::new(
+                Point::new(0.0, 0.0, -2.5),
+                Vector::new(0.0, 0.0, 0.5)
+            ))
+        );
+    }
+    #[test]
+    fn intersect_translated_shape() {
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let shape = TestShape::new().with_transform(translation(5.0, 0.0, 0.0));
+        let _ = shape.intersect(&ray);
+        assert_eq!(
+            *shape.saved_ray.borrow(),
+            Some(Ray::new(Point::new(-5.0, 0.0, -5.0), Vector::z_unit()))
+        );
+    }
+    #[test]
+    fn normal_on_translated_shape() {
+        let shape = TestShape::new().with_transform(translation(0.0, 1.0, 0.0));
+        let normal = shape.normal_at(
+            Point::new(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            &Intersection::new(0.0, &shape),
+        );
+        assert!(approx_eq!(
+            Vector,
+            normal,
+            Vector::new(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            epsilon = 0.00001
+        ));
+    }
+    #[test]
+    fn normal_on_transformed_shape() {
+        let shape = TestShape::new().with_transform(scaling(1.0, 0.5, 1.0) * rotation_z(PI / 5.0));
+        let normal = shape.normal_at(
+            Point::new(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            &Intersection::new(0.0, &shape),
+        );
+        assert!(approx_eq!(
+            Vector,
+            normal,
+            Vector::new(0.0, 0.97014, -0.24254),
+            epsilon = 0.00001
+        ));
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{
+    intersection::Intersection, material::Material, matrix::Matrix4, point::Point, ray::Ray,
+    shape::Shape, triangle::moller_trumbore, vector::Vector,
+};
+
+/// Triangle with a normal per corner, interpolated across the face using the
+/// barycentric `u`/`v` recorded on each intersection.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SmoothTriangle {
+    transform: Matrix4,
+    inverse: Matrix4,
+    material: Material,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    n1: Vector,
+    n2: Vector,
+    n3: Vector,
+    e1: Vector,
+    e2: Vector,
+}
+
+impl SmoothTriangle {
+    pub fn new(p1: Point, p2: Point, p3: Point, n1: Vector, n2: Vector, n3: Vector) -> Self {
+        SmoothTriangle {
+            transform: Matrix4::identity(),
+            inverse: Matrix4::identity(),
+            material: Material::default(),
+            p1,
+            p2,
+            p3,
+            n1,
+            n2,
+            n3,
+            e1: p2 - p1,
+            e2: p3 - p1,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn p1(&self) -> Point {
+        self.p1
+    }
+
+    #[allow(dead_code)]
+    pub fn p2(&self) -> Point {
+        self.p2
+    }
+
+    #[allow(dead_code)]
+    pub fn p3(&self) -> Point {
+        self.p3
+    }
+
+    #[allow(dead_code)]
+    pub fn n1(&self) -> Vector {
+        self.n1
+    }
+
+    #[allow(dead_code)]
+    pub fn n2(&self) -> Vector {
+        self.n2
+    }
+
+    #[allow(dead_code)]
+    pub fn n3(&self) -> Vector {
+        self.n3
+    }
+}
+
+impl Shape for SmoothTriangle {
+    fn transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn inverse(&self) -> Matrix4 {
+        self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix4) {
+        self.inverse = transform
+            .inverse()
+            .expect("smooth triangle transform must be invertible");
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        match moller_trumbore(ray, self.p1, self.e1, self.e2) {
+            Some((t, u, v)) => vec![Intersection::with_uv(t, self, u, v)],
+            None => vec![],
+        }
+    }
+
+    fn local_normal_at(&self, _point: Point, hit: &Intersection) -> Vector {
+        self.n2 * hit.u + self.n3 * hit.v + self.n1 * (1.0 - hit.u - hit.v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+
+    use crate::{
+        intersection::{Intersection, Intersections},
+        point::Point,
+        ray::Ray,
+        shape::Shape,
+        smooth_triangle::SmoothTriangle,
+        vector::Vector,
+    };
+
+    fn test_triangle() -> SmoothTriangle {
+        SmoothTriangle::new(
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Vector::y_unit(),
+            Vector::new(-1.0, 0.0, 0.0),
+            Vector::x_unit(),
+        )
+    }
+
+    #[test]
+    fn construct_smooth_triangle() {
+        let triangle = test_triangle();
+        assert_eq!(triangle.p1(), Point::new(0.0, 1.0, 0.0));
+        assert_eq!(triangle.p2(), Point::new(-1.0, 0.0, 0.0));
+        assert_eq!(triangle.p3(), Point::new(1.0, 0.0, 0.0));
+        assert_eq!(triangle.n1(), Vector::y_unit());
+        assert_eq!(triangle.n2(), Vector::new(-1.0, 0.0, 0.0));
+        assert_eq!(triangle.n3(), Vector::x_unit());
+    }
+    #[test]
+    fn intersection_stores_u_and_v() {
+        let triangle = test_triangle();
+        let ray = Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::z_unit());
+        let intersections = triangle.local_intersect(&ray);
+        assert!(approx_eq!(f32, intersections[0].u, 0.45, epsilon = 0.0001));
+        assert!(approx_eq!(f32, intersections[0].v, 0.25, epsilon = 0.0001));
+    }
+    #[test]
+    fn normal_is_interpolated_with_u_and_v() {
+        let triangle = test_triangle();
+        let hit = Intersection::with_uv(1.0, &triangle, 0.45, 0.25);
+        assert!(approx_eq!(
+            Vector,
+            triangle.normal_at(Point::origin(), &hit),
+            Vector::new(-0.5547, 0.83205, 0.0),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn prepare_computations_interpolates_normal() {
+        let triangle = test_triangle();
+        let ray = Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::z_unit());
+        let intersections =
+            Intersections::new(vec![Intersection::with_uv(1.0, &triangle, 0.45, 0.25)]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(approx_eq!(
+            Vector,
+            comps.normal,
+            Vector::new(-0.5547, 0.83205, 0.0),
+            epsilon = 0.0001
+        ));
+    }
+}
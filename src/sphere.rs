@@ -0,0 +1,194 @@
+use crate::{
+    intersection::Intersection, material::Material, matrix::Matrix4, point::Point, ray::Ray,
+    shape::Shape, vector::Vector,
+};
+
+/// Unit sphere centered on the origin.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Sphere {
+    transform: Matrix4,
+    inverse: Matrix4,
+    material: Material,
+}
+
+impl Sphere {
+    pub fn new() -> Self {
+        Sphere {
+            transform: Matrix4::identity(),
+            inverse: Matrix4::identity(),
+            material: Material::default(),
+        }
+    }
+}
+
+impl Default for Sphere {
+    fn default() -> Self {
+        Sphere::new()
+    }
+}
+
+impl Shape for Sphere {
+    fn transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn inverse(&self) -> Matrix4 {
+        self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix4) {
+        self.inverse = transform
+            .inverse()
+            .expect("sphere transform must be invertible");
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    // `^` is the dot product, so squaring a vector through it is intended
+    #[allow(clippy::eq_op)]
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let sphere_to_ray = ray.origin - Point::origin();
+        let a = ray.direction ^ ray.direction;
+        let b = 2.0 * (ray.direction ^ sphere_to_ray);
+        let c = (sphere_to_ray ^ sphere_to_ray) - 1.0;
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return vec![];
+        }
+        let root = discriminant.sqrt();
+        vec![
+            Intersection::new((-b - root) / (2.0 * a), self),
+            Intersection::new((-b + root) / (2.0 * a), self),
+        ]
+    }
+
+    fn local_normal_at(&self, point: Point, _hit: &Intersection) -> Vector {
+        point - Point::origin()
+    }
+}
+
+#[cfg(test)]
+impl Sphere {
+    /// Unit sphere made of fully transparent glass.
+    pub fn glass() -> Self {
+        Sphere::new().with_material(Material {
+            transparency: 1.0,
+            refractive_index: 1.5,
+            ..Material::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{FRAC_1_SQRT_2, PI};
+
+    use float_cmp::approx_eq;
+
+    use crate::{
+        intersection::Intersection,
+        material::Material,
+        matrix::Matrix4,
+        point::Point,
+        shape::Shape,
+        sphere::Sphere,
+        transform::{rotation_z, scaling, translation},
+        vector::Vector,
+    };
+
+    #[test]
+    fn glass_sphere() {
+        let sphere = Sphere::glass();
+        assert_eq!(sphere.transform(), Matrix4::identity());
+        assert_eq!(sphere.material().transparency, 1.0);
+        assert_eq!(sphere.material().refractive_index, 1.5);
+    }
+    #[test]
+    fn sphere_default_transform() {
+        let sphere = Sphere::new();
+        assert_eq!(sphere.transform(), Matrix4::identity());
+    }
+    #[test]
+    fn change_sphere_transform() {
+        let mut sphere = Sphere::new();
+        sphere.set_transform(translation(2.0, 3.0, 4.0));
+        assert_eq!(sphere.transform(), translation(2.0, 3.0, 4.0));
+        assert_eq!(sphere.inverse(), translation(-2.0, -3.0, -4.0));
+    }
+    #[test]
+    fn sphere_default_material() {
+        let sphere = Sphere::new();
+        assert_eq!(*sphere.material(), Material::default());
+    }
+    #[test]
+    fn assign_sphere_material() {
+        let material = Material {
+            ambient: 1.0,
+            ..Material::default()
+        };
+        let sphere = Sphere::new().with_material(material.clone());
+        assert_eq!(*sphere.material(), material);
+    }
+    #[test]
+    fn normal_on_sphere_axes() {
+        let sphere = Sphere::new();
+        assert_eq!(
+            sphere.normal_at(Point::new(1.0, 0.0, 0.0), &Intersection::new(0.0, &sphere)),
+            Vector::x_unit()
+        );
+        assert_eq!(
+            sphere.normal_at(Point::new(0.0, 1.0, 0.0), &Intersection::new(0.0, &sphere)),
+            Vector::y_unit()
+        );
+        assert_eq!(
+            sphere.normal_at(Point::new(0.0, 0.0, 1.0), &Intersection::new(0.0, &sphere)),
+            Vector::z_unit()
+        );
+    }
+    #[test]
+    fn normal_on_sphere_is_normalized() {
+        let sphere = Sphere::new();
+        let coordinate = 3.0_f32.sqrt() / 3.0;
+        let normal = sphere.normal_at(
+            Point::new(coordinate, coordinate, coordinate),
+            &Intersection::new(0.0, &sphere),
+        );
+        assert_eq!(normal, Vector::new(coordinate, coordinate, coordinate));
+        assert_eq!(normal, normal.normalize());
+    }
+    #[test]
+    fn normal_on_translated_sphere() {
+        let sphere = Sphere::new().with_transform(translation(0.0, 1.0, 0.0));
+        let normal = sphere.normal_at(
+            Point::new(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            &Intersection::new(0.0, &sphere),
+        );
+        assert!(approx_eq!(
+            Vector,
+            normal,
+            Vector::new(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            epsilon = 0.00001
+        ));
+    }
+    #[test]
+    fn normal_on_transformed_sphere() {
+        let sphere = Sphere::new().with_transform(scaling(1.0, 0.5, 1.0) * rotation_z(PI / 5.0));
+        let normal = sphere.normal_at(
+            Point::new(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            &Intersection::new(0.0, &sphere),
+        );
+        assert!(approx_eq!(
+            Vector,
+            normal,
+            Vector::new(0.0, 0.97014, -0.24254),
+            epsilon = 0.00001
+        ));
+    }
+}
@@ -0,0 +1,133 @@
+use std::{fmt, str::FromStr};
+
+use crate::color::Color;
+
+/// Compresses HDR colors into 0.0..=1.0 before 8-bit output.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub(crate) enum ToneMap {
+    /// Clip each channel; anything brighter than 1.0 is lost
+    #[default]
+    Clamp,
+    /// `c / (1 + c)`: keeps detail in highlights at the cost of contrast
+    Reinhard,
+    /// Narkowicz's fit of the ACES filmic curve
+    Aces,
+    /// `1 - e^(-c * exposure)`, like film exposed for the given time
+    Exposure(f32),
+}
+
+impl ToneMap {
+    pub fn apply(self, color: Color) -> Color {
+        match self {
+            ToneMap::Clamp => color.map(|c| c.clamp(0.0, 1.0)),
+            ToneMap::Reinhard => color.map(|c| {
+                let c = c.max(0.0);
+                c / (1.0 + c)
+            }),
+            ToneMap::Aces => color.map(|c| {
+                let c = c.max(0.0);
+                let mapped = (c * (2.51 * c + 0.03)) / (c * (2.43 * c + 0.59) + 0.14);
+                mapped.clamp(0.0, 1.0)
+            }),
+            ToneMap::Exposure(exposure) => color.map(|c| 1.0 - (-c.max(0.0) * exposure).exp()),
+        }
+    }
+}
+
+/// A tone map name that `ToneMap::from_str` doesn't recognize.
+#[derive(Debug, PartialEq)]
+pub(crate) struct ParseToneMapError(String);
+
+impl fmt::Display for ParseToneMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown tone map `{}` (expected clamp, reinhard, aces or exposure=<n>)",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseToneMapError {}
+
+/// Parses the names used on the command line: `clamp`, `reinhard`, `aces`,
+/// or `exposure=<n>`.
+impl FromStr for ToneMap {
+    type Err = ParseToneMapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "clamp" => Ok(ToneMap::Clamp),
+            "reinhard" => Ok(ToneMap::Reinhard),
+            "aces" => Ok(ToneMap::Aces),
+            other => other
+                .strip_prefix("exposure=")
+                .and_then(|exposure| exposure.parse().ok())
+                .filter(|exposure: &f32| *exposure > 0.0)
+                .map(ToneMap::Exposure)
+                .ok_or_else(|| ParseToneMapError(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+
+    use crate::{
+        color::{Color, BLACK, WHITE},
+        tone_map::{ParseToneMapError, ToneMap},
+    };
+
+    #[test]
+    fn clamp_clips_channels() {
+        assert_eq!(
+            ToneMap::Clamp.apply(Color::new(-0.5, 0.5, 2.0)),
+            Color::new(0.0, 0.5, 1.0)
+        );
+        assert_eq!(ToneMap::default(), ToneMap::Clamp);
+    }
+    #[test]
+    fn reinhard_compresses_highlights() {
+        assert_eq!(
+            ToneMap::Reinhard.apply(Color::new(1.0, 3.0, -1.0)),
+            Color::new(0.5, 0.75, 0.0)
+        );
+    }
+    #[test]
+    fn aces_maps_into_unit_range() {
+        assert_eq!(ToneMap::Aces.apply(BLACK), BLACK);
+        assert!(approx_eq!(
+            Color,
+            ToneMap::Aces.apply(WHITE),
+            Color::new(0.8038, 0.8038, 0.8038),
+            epsilon = 0.0001
+        ));
+        assert_eq!(ToneMap::Aces.apply(WHITE * 100.0), WHITE);
+    }
+    #[test]
+    fn exposure_scales_response() {
+        let color = Color::new(0.0, 1.0, 2.0);
+        assert!(approx_eq!(
+            Color,
+            ToneMap::Exposure(1.0).apply(color),
+            Color::new(0.0, 0.63212, 0.86466),
+            epsilon = 0.0001
+        ));
+        let brighter = ToneMap::Exposure(2.0).apply(color);
+        assert!(brighter.g > 0.86 && brighter.b < 1.0);
+    }
+    #[test]
+    fn parse_names() {
+        assert_eq!("clamp".parse(), Ok(ToneMap::Clamp));
+        assert_eq!("Reinhard".parse(), Ok(ToneMap::Reinhard));
+        assert_eq!("aces".parse(), Ok(ToneMap::Aces));
+        assert_eq!("exposure=1.5".parse(), Ok(ToneMap::Exposure(1.5)));
+        for bad in ["filmic", "exposure", "exposure=x", "exposure=-1"] {
+            assert_eq!(
+                bad.parse::<ToneMap>(),
+                Err(ParseToneMapError(bad.to_string()))
+            );
+        }
+    }
+}
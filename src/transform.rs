@@ -0,0 +1,291 @@
+use crate::{matrix::Matrix4, vector::Vector};
+
+pub(crate) fn translation(x: f32, y: f32, z: f32) -> Matrix4 {
+    Matrix4::new([
+        [1.0, 0.0, 0.0, x],
+        [0.0, 1.0, 0.0, y],
+        [0.0, 0.0, 1.0, z],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+pub(crate) fn scaling(x: f32, y: f32, z: f32) -> Matrix4 {
+    Matrix4::new([
+        [x, 0.0, 0.0, 0.0],
+        [0.0, y, 0.0, 0.0],
+        [0.0, 0.0, z, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+pub(crate) fn rotation_x(radians: f32) -> Matrix4 {
+    let (sin, cos) = radians.sin_cos();
+    Matrix4::new([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, cos, -sin, 0.0],
+        [0.0, sin, cos, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+pub(crate) fn rotation_y(radians: f32) -> Matrix4 {
+    let (sin, cos) = radians.sin_cos();
+    Matrix4::new([
+        [cos, 0.0, sin, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [-sin, 0.0, cos, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+pub(crate) fn rotation_z(radians: f32) -> Matrix4 {
+    let (sin, cos) = radians.sin_cos();
+    Matrix4::new([
+        [cos, -sin, 0.0, 0.0],
+        [sin, cos, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Rotation about an arbitrary axis through the origin (Rodrigues' formula).
+pub(crate) fn rotation(axis: Vector, radians: f32) -> Matrix4 {
+    let Vector { x, y, z, .. } = axis.normalize();
+    let (sin, cos) = radians.sin_cos();
+    let t = 1.0 - cos;
+    Matrix4::new([
+        [
+            t * x * x + cos,
+            t * x * y - sin * z,
+            t * x * z + sin * y,
+            0.0,
+        ],
+        [
+            t * x * y + sin * z,
+            t * y * y + cos,
+            t * y * z - sin * x,
+            0.0,
+        ],
+        [
+            t * x * z - sin * y,
+            t * y * z + sin * x,
+            t * z * z + cos,
+            0.0,
+        ],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+pub(crate) fn shearing(xy: f32, xz: f32, yx: f32, yz: f32, zx: f32, zy: f32) -> Matrix4 {
+    Matrix4::new([
+        [1.0, xy, xz, 0.0],
+        [yx, 1.0, yz, 0.0],
+        [zx, zy, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Chainable transformation builder. Each call is applied after the ones
+/// before it, so the chain reads in the order the operations happen.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct Transform {
+    matrix: Matrix4,
+}
+
+impl Transform {
+    pub fn identity() -> Self {
+        Transform {
+            matrix: Matrix4::identity(),
+        }
+    }
+
+    fn then(self, next: Matrix4) -> Self {
+        Transform {
+            matrix: next * self.matrix,
+        }
+    }
+
+    pub fn translate(self, x: f32, y: f32, z: f32) -> Self {
+        self.then(translation(x, y, z))
+    }
+
+    pub fn scale(self, x: f32, y: f32, z: f32) -> Self {
+        self.then(scaling(x, y, z))
+    }
+
+    pub fn rotate_x(self, radians: f32) -> Self {
+        self.then(rotation_x(radians))
+    }
+
+    pub fn rotate_y(self, radians: f32) -> Self {
+        self.then(rotation_y(radians))
+    }
+
+    #[allow(dead_code)]
+    pub fn rotate_z(self, radians: f32) -> Self {
+        self.then(rotation_z(radians))
+    }
+
+    #[allow(dead_code)]
+    pub fn rotate(self, axis: Vector, radians: f32) -> Self {
+        self.then(rotation(axis, radians))
+    }
+
+    #[allow(dead_code)]
+    pub fn shear(self, xy: f32, xz: f32, yx: f32, yz: f32, zx: f32, zy: f32) -> Self {
+        self.then(shearing(xy, xz, yx, yz, zx, zy))
+    }
+
+    pub fn build(self) -> Matrix4 {
+        self.matrix
+    }
+}
+
+impl From<Transform> for Matrix4 {
+    fn from(t: Transform) -> Self {
+        t.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use crate::{point::Point, transform::*, vector::Vector};
+
+    #[test]
+    fn translate_point() {
+        let transform = translation(5.0, -3.0, 2.0);
+        let point = Point::new(-3.0, 4.0, 5.0);
+        assert_eq!(transform * point, Point::new(2.0, 1.0, 7.0));
+    }
+    #[test]
+    fn translate_point_by_inverse() {
+        let inverse = translation(5.0, -3.0, 2.0).inverse().unwrap();
+        let point = Point::new(-3.0, 4.0, 5.0);
+        assert_eq!(inverse * point, Point::new(-8.0, 7.0, 3.0));
+    }
+    #[test]
+    fn translation_does_not_affect_vector() {
+        let transform = translation(5.0, -3.0, 2.0);
+        let vector = Vector::new(-3.0, 4.0, 5.0);
+        assert_eq!(transform * vector, vector);
+    }
+    #[test]
+    fn scale_point_and_vector() {
+        let transform = scaling(2.0, 3.0, 4.0);
+        assert_eq!(
+            transform * Point::new(-4.0, 6.0, 8.0),
+            Point::new(-8.0, 18.0, 32.0)
+        );
+        assert_eq!(
+            transform * Vector::new(-4.0, 6.0, 8.0),
+            Vector::new(-8.0, 18.0, 32.0)
+        );
+    }
+    #[test]
+    fn scale_vector_by_inverse() {
+        let inverse = scaling(2.0, 3.0, 4.0).inverse().unwrap();
+        let vector = Vector::new(-4.0, 6.0, 8.0);
+        assert_eq!(inverse * vector, Vector::new(-2.0, 2.0, 2.0));
+    }
+    #[test]
+    fn reflect_by_negative_scale() {
+        let transform = scaling(-1.0, 1.0, 1.0);
+        let point = Point::new(2.0, 3.0, 4.0);
+        assert_eq!(transform * point, Point::new(-2.0, 3.0, 4.0));
+    }
+    #[test]
+    fn rotate_point_around_x() {
+        let point = Point::new(0.0, 1.0, 0.0);
+        assert_eq!(
+            rotation_x(PI / 4.0) * point,
+            Point::new(0.0, 2.0_f32.sqrt() / 2.0, 2.0_f32.sqrt() / 2.0)
+        );
+        assert_eq!(rotation_x(PI / 2.0) * point, Point::new(0.0, 0.0, 1.0));
+    }
+    #[test]
+    fn rotate_point_around_y() {
+        let point = Point::new(0.0, 0.0, 1.0);
+        assert_eq!(
+            rotation_y(PI / 4.0) * point,
+            Point::new(2.0_f32.sqrt() / 2.0, 0.0, 2.0_f32.sqrt() / 2.0)
+        );
+        assert_eq!(rotation_y(PI / 2.0) * point, Point::new(1.0, 0.0, 0.0));
+    }
+    #[test]
+    fn rotate_point_around_z() {
+        let point = Point::new(0.0, 1.0, 0.0);
+        assert_eq!(
+            rotation_z(PI / 4.0) * point,
+            Point::new(-(2.0_f32.sqrt()) / 2.0, 2.0_f32.sqrt() / 2.0, 0.0)
+        );
+        assert_eq!(rotation_z(PI / 2.0) * point, Point::new(-1.0, 0.0, 0.0));
+    }
+    #[test]
+    fn rotate_around_arbitrary_axis() {
+        assert_eq!(rotation(Vector::x_unit(), PI / 3.0), rotation_x(PI / 3.0));
+        assert_eq!(
+            rotation(Vector::new(0.0, 2.0, 0.0), PI / 3.0),
+            rotation_y(PI / 3.0)
+        );
+        let point = Point::new(1.0, 0.0, 0.0);
+        let transform = rotation(Vector::new(1.0, 1.0, 1.0), 2.0 * PI / 3.0);
+        assert_eq!(transform * point, Point::new(0.0, 1.0, 0.0));
+    }
+    #[test]
+    fn shear_point() {
+        let point = Point::new(2.0, 3.0, 4.0);
+        assert_eq!(
+            shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0) * point,
+            Point::new(5.0, 3.0, 4.0)
+        );
+        assert_eq!(
+            shearing(0.0, 1.0, 0.0, 0.0, 0.0, 0.0) * point,
+            Point::new(6.0, 3.0, 4.0)
+        );
+        assert_eq!(
+            shearing(0.0, 0.0, 1.0, 0.0, 0.0, 0.0) * point,
+            Point::new(2.0, 5.0, 4.0)
+        );
+        assert_eq!(
+            shearing(0.0, 0.0, 0.0, 1.0, 0.0, 0.0) * point,
+            Point::new(2.0, 7.0, 4.0)
+        );
+        assert_eq!(
+            shearing(0.0, 0.0, 0.0, 0.0, 1.0, 0.0) * point,
+            Point::new(2.0, 3.0, 6.0)
+        );
+        assert_eq!(
+            shearing(0.0, 0.0, 0.0, 0.0, 0.0, 1.0) * point,
+            Point::new(2.0, 3.0, 7.0)
+        );
+    }
+    #[test]
+    fn chained_transforms_apply_in_order() {
+        let point = Point::new(1.0, 0.0, 1.0);
+        let transform = Transform::identity()
+            .rotate_x(PI / 2.0)
+            .scale(5.0, 5.0, 5.0)
+            .translate(10.0, 5.0, 7.0)
+            .build();
+        assert_eq!(
+            transform,
+            translation(10.0, 5.0, 7.0) * scaling(5.0, 5.0, 5.0) * rotation_x(PI / 2.0)
+        );
+        assert_eq!(transform * point, Point::new(15.0, 0.0, 7.0));
+    }
+    #[test]
+    fn chained_translation_does_not_affect_vector() {
+        let transform: Matrix4 = Transform::identity()
+            .scale(2.0, 2.0, 2.0)
+            .translate(5.0, -3.0, 2.0)
+            .into();
+        let vector = Vector::new(-3.0, 4.0, 5.0);
+        assert_eq!(transform * vector, Vector::new(-6.0, 8.0, 10.0));
+        assert_eq!(
+            transform * Point::new(-3.0, 4.0, 5.0),
+            Point::new(-1.0, 5.0, 12.0)
+        );
+    }
+}
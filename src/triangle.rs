@@ -0,0 +1,216 @@
+use crate::{
+    intersection::Intersection, material::Material, matrix::Matrix4, point::Point, ray::Ray,
+    shape::Shape, vector::Vector, EPSILON,
+};
+
+/// Flat-shaded triangle. Edges and the face normal are precomputed from the
+/// corners, so the corners can't change after construction.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Triangle {
+    transform: Matrix4,
+    inverse: Matrix4,
+    material: Material,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    e1: Vector,
+    e2: Vector,
+    normal: Vector,
+}
+
+impl Triangle {
+    pub fn new(p1: Point, p2: Point, p3: Point) -> Self {
+        let e1 = p2 - p1;
+        let e2 = p3 - p1;
+        Triangle {
+            transform: Matrix4::identity(),
+            inverse: Matrix4::identity(),
+            material: Material::default(),
+            p1,
+            p2,
+            p3,
+            e1,
+            e2,
+            normal: (e2 * e1).normalize(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn p1(&self) -> Point {
+        self.p1
+    }
+
+    #[allow(dead_code)]
+    pub fn p2(&self) -> Point {
+        self.p2
+    }
+
+    #[allow(dead_code)]
+    pub fn p3(&self) -> Point {
+        self.p3
+    }
+
+    #[allow(dead_code)]
+    pub fn e1(&self) -> Vector {
+        self.e1
+    }
+
+    #[allow(dead_code)]
+    pub fn e2(&self) -> Vector {
+        self.e2
+    }
+
+    #[allow(dead_code)]
+    pub fn normal(&self) -> Vector {
+        self.normal
+    }
+}
+
+/// Möller–Trumbore ray/triangle test for the triangle at `p1` spanned by
+/// edges `e1` and `e2`. Returns `(t, u, v)` where `u` and `v` are the
+/// barycentric weights of the second and third corners.
+pub(crate) fn moller_trumbore(
+    ray: &Ray,
+    p1: Point,
+    e1: Vector,
+    e2: Vector,
+) -> Option<(f32, f32, f32)> {
+    let dir_cross_e2 = ray.direction * e2;
+    let determinant = e1 ^ dir_cross_e2;
+    // Parallel to the triangle's plane. The determinant grows with the edge
+    // lengths, so compare it relative to them; otherwise small triangles from
+    // dense meshes would always count as parallel
+    let scale = ray.direction.magnitude() * e1.magnitude() * e2.magnitude();
+    if determinant.abs() < EPSILON * scale {
+        return None;
+    }
+    let f = 1.0 / determinant;
+    let p1_to_origin = ray.origin - p1;
+    let u = f * (p1_to_origin ^ dir_cross_e2);
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let origin_cross_e1 = p1_to_origin * e1;
+    let v = f * (ray.direction ^ origin_cross_e1);
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    Some((f * (e2 ^ origin_cross_e1), u, v))
+}
+
+impl Shape for Triangle {
+    fn transform(&self) -> Matrix4 {
+        self.transform
+    }
+
+    fn inverse(&self) -> Matrix4 {
+        self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix4) {
+        self.inverse = transform
+            .inverse()
+            .expect("triangle transform must be invertible");
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        match moller_trumbore(ray, self.p1, self.e1, self.e2) {
+            Some((t, u, v)) => vec![Intersection::with_uv(t, self, u, v)],
+            None => vec![],
+        }
+    }
+
+    fn local_normal_at(&self, _point: Point, _hit: &Intersection) -> Vector {
+        self.normal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+
+    use crate::{
+        intersection::Intersection, point::Point, ray::Ray, shape::Shape, triangle::Triangle,
+        vector::Vector,
+    };
+
+    fn test_triangle() -> Triangle {
+        Triangle::new(
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn construct_triangle() {
+        let triangle = test_triangle();
+        assert_eq!(triangle.p1(), Point::new(0.0, 1.0, 0.0));
+        assert_eq!(triangle.p2(), Point::new(-1.0, 0.0, 0.0));
+        assert_eq!(triangle.p3(), Point::new(1.0, 0.0, 0.0));
+        assert_eq!(triangle.e1(), Vector::new(-1.0, -1.0, 0.0));
+        assert_eq!(triangle.e2(), Vector::new(1.0, -1.0, 0.0));
+        assert_eq!(triangle.normal(), Vector::new(0.0, 0.0, -1.0));
+    }
+    #[test]
+    fn normal_is_constant_across_triangle() {
+        let triangle = test_triangle();
+        let hit = Intersection::new(0.0, &triangle);
+        for point in [
+            Point::new(0.0, 0.5, 0.0),
+            Point::new(-0.5, 0.75, 0.0),
+            Point::new(0.5, 0.25, 0.0),
+        ] {
+            assert_eq!(triangle.local_normal_at(point, &hit), triangle.normal());
+        }
+    }
+    #[test]
+    fn ray_parallel_to_triangle() {
+        let triangle = test_triangle();
+        let ray = Ray::new(Point::new(0.0, -1.0, -2.0), Vector::y_unit());
+        assert!(triangle.local_intersect(&ray).is_empty());
+    }
+    #[test]
+    fn ray_misses_each_edge() {
+        let triangle = test_triangle();
+        for origin in [
+            Point::new(1.0, 1.0, -2.0),
+            Point::new(-1.0, 1.0, -2.0),
+            Point::new(0.0, -1.0, -2.0),
+        ] {
+            let ray = Ray::new(origin, Vector::z_unit());
+            assert!(triangle.local_intersect(&ray).is_empty());
+        }
+    }
+    #[test]
+    fn ray_strikes_triangle() {
+        let triangle = test_triangle();
+        let ray = Ray::new(Point::new(0.0, 0.5, -2.0), Vector::z_unit());
+        let intersections = triangle.local_intersect(&ray);
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections[0].t, 2.0);
+    }
+    #[test]
+    fn ray_strikes_small_triangle() {
+        let triangle = Triangle::new(
+            Point::new(3.0, 2.005, 1.0),
+            Point::new(2.995, 2.0, 1.0),
+            Point::new(3.005, 2.0, 1.0),
+        );
+        let ray = Ray::new(Point::new(3.0, 2.0025, -1.0), Vector::z_unit());
+        let intersections = triangle.local_intersect(&ray);
+        assert_eq!(intersections.len(), 1);
+        assert!(approx_eq!(f32, intersections[0].t, 2.0, epsilon = 0.0001));
+        let ray = Ray::new(Point::new(3.0, 1.9, 1.0), Vector::y_unit());
+        assert!(triangle.local_intersect(&ray).is_empty());
+    }
+}
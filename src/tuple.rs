@@ -138,6 +138,7 @@ mod tests {
     use crate::{point::Point, tuple::Tuple, vector::Vector};
 
     #[test]
+    #[allow(clippy::neg_multiply)]
     fn two_tuples_approx_eq() {
         let tuple1 = Tuple::new(0.0 - 0.5 + 0.3, 2.2, 3.0 / 1.5, -4.0);
         let tuple2 = Tuple::new(-0.2, 1.1 * 2.0, 2.0, 4.0 * -1.0);
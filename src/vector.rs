@@ -1,5 +1,5 @@
 use crate::{point::Point, tuple::Tuple};
-use float_cmp::approx_eq;
+use float_cmp::{approx_eq, ApproxEq, F32Margin};
 use std::fmt;
 
 #[derive(Copy, Clone, Debug)]
@@ -24,6 +24,7 @@ impl Vector {
         Vector::new(0.0, 0.0, 0.0)
     }
 
+    #[allow(dead_code)]
     pub fn x_unit() -> Self {
         Vector::new(1.0, 0.0, 0.0)
     }
@@ -32,6 +33,7 @@ impl Vector {
         Vector::new(0.0, 1.0, 0.0)
     }
 
+    #[allow(dead_code)]
     pub fn z_unit() -> Self {
         Vector::new(0.0, 0.0, 1.0)
     }
@@ -44,6 +46,10 @@ impl Vector {
         let magnitude = self.magnitude();
         self / magnitude
     }
+
+    pub fn reflect(self, normal: Vector) -> Self {
+        self - normal * 2.0 * (self ^ normal)
+    }
 }
 
 impl fmt::Display for Vector {
@@ -70,6 +76,17 @@ impl PartialEq for Vector {
     }
 }
 
+impl ApproxEq for Vector {
+    type Margin = F32Margin;
+
+    fn approx_eq<M: Into<Self::Margin>>(self, other: Self, margin: M) -> bool {
+        let margin = margin.into();
+        self.x.approx_eq(other.x, margin)
+            && self.y.approx_eq(other.y, margin)
+            && self.z.approx_eq(other.z, margin)
+    }
+}
+
 impl std::ops::Add<Vector> for Vector {
     type Output = Vector;
 
@@ -164,6 +181,8 @@ impl std::ops::Mul<Vector> for Vector {
 
 #[cfg(test)]
 mod tests {
+    use float_cmp::approx_eq;
+
     use crate::{point::Point, tuple::Tuple, vector::Vector};
 
     #[test]
@@ -284,4 +303,21 @@ mod tests {
         assert_eq!(cross_product1, Vector::new(-1.0, 2.0, -1.0));
         assert_eq!(cross_product2, Vector::new(1.0, -2.0, 1.0));
     }
+    #[test]
+    fn reflect_vector_approaching_at_45_degrees() {
+        let vector = Vector::new(1.0, -1.0, 0.0);
+        let normal = Vector::y_unit();
+        assert_eq!(vector.reflect(normal), Vector::new(1.0, 1.0, 0.0));
+    }
+    #[test]
+    fn reflect_vector_off_slanted_surface() {
+        let vector = Vector::new(0.0, -1.0, 0.0);
+        let normal = Vector::new(2.0_f32.sqrt() / 2.0, 2.0_f32.sqrt() / 2.0, 0.0);
+        assert!(approx_eq!(
+            Vector,
+            vector.reflect(normal),
+            Vector::x_unit(),
+            epsilon = 0.00001
+        ));
+    }
 }
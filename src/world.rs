@@ -0,0 +1,578 @@
+use crate::{
+    color::{Color, BLACK},
+    intersection::{Computations, Intersections},
+    light::{lighting, PointLight},
+    point::Point,
+    ray::Ray,
+    shape::Shape,
+};
+
+#[derive(Debug)]
+pub(crate) struct World {
+    pub objects: Vec<Box<dyn Shape>>,
+    pub lights: Vec<PointLight>,
+    /// How many bounces a ray may take before reflections are cut off
+    pub max_depth: usize,
+}
+
+impl World {
+    pub fn new() -> Self {
+        World {
+            objects: vec![],
+            lights: vec![],
+            max_depth: 5,
+        }
+    }
+
+    /// Every intersection of `ray` with every object, sorted by `t`.
+    pub fn intersect_world(&self, ray: &Ray) -> Intersections<'_> {
+        self.objects
+            .iter()
+            .flat_map(|object| object.intersect(ray))
+            .collect()
+    }
+
+    /// Shades a hit, following at most `remaining` further reflections.
+    pub fn shade_hit(&self, comps: &Computations, remaining: usize) -> Color {
+        let surface = self.lights.iter().fold(BLACK, |color, light| {
+            color
+                + lighting(
+                    comps.object.material(),
+                    comps.object,
+                    light,
+                    comps.over_point,
+                    comps.eye,
+                    comps.normal,
+                    self.is_shadowed(light, comps.over_point),
+                )
+        });
+        let reflected = self.reflected_color(comps, remaining);
+        let refracted = self.refracted_color(comps, remaining);
+        let material = comps.object.material();
+        if material.reflective > 0.0 && material.transparency > 0.0 {
+            let reflectance = comps.schlick();
+            surface + reflected * reflectance + refracted * (1.0 - reflectance)
+        } else {
+            surface + reflected + refracted
+        }
+    }
+
+    pub fn reflected_color(&self, comps: &Computations, remaining: usize) -> Color {
+        let reflective = comps.object.material().reflective;
+        if remaining == 0 || reflective == 0.0 {
+            return BLACK;
+        }
+        let reflect_ray = Ray::new(comps.over_point, comps.reflect_vector);
+        self.color_at_depth(&reflect_ray, remaining - 1) * reflective
+    }
+
+    pub fn refracted_color(&self, comps: &Computations, remaining: usize) -> Color {
+        let transparency = comps.object.material().transparency;
+        if remaining == 0 || transparency == 0.0 {
+            return BLACK;
+        }
+        // Snell's law
+        let n_ratio = comps.n1 / comps.n2;
+        let cos_i = comps.eye ^ comps.normal;
+        let sin2_t = n_ratio * n_ratio * (1.0 - cos_i * cos_i);
+        if sin2_t > 1.0 {
+            // Total internal reflection
+            return BLACK;
+        }
+        let cos_t = (1.0 - sin2_t).sqrt();
+        let direction = comps.normal * (n_ratio * cos_i - cos_t) - comps.eye * n_ratio;
+        let refract_ray = Ray::new(comps.under_point, direction);
+        self.color_at_depth(&refract_ray, remaining - 1) * transparency
+    }
+
+    /// Whether anything lies between `point` and `light`.
+    pub fn is_shadowed(&self, light: &PointLight, point: Point) -> bool {
+        let to_light = light.position - point;
+        let distance = to_light.magnitude();
+        let ray = Ray::new(point, to_light.normalize());
+        match self.intersect_world(&ray).hit() {
+            Some(hit) => hit.t < distance,
+            None => false,
+        }
+    }
+
+    pub fn color_at(&self, ray: &Ray) -> Color {
+        self.color_at_depth(ray, self.max_depth)
+    }
+
+    pub fn color_at_depth(&self, ray: &Ray, remaining: usize) -> Color {
+        let intersections = self.intersect_world(ray);
+        match intersections.hit() {
+            Some(hit) => self.shade_hit(&hit.prepare_computations(ray, &intersections), remaining),
+            None => BLACK,
+        }
+    }
+}
+
+impl Default for World {
+    fn default() -> Self {
+        World::new()
+    }
+}
+
+#[cfg(test)]
+impl World {
+    /// Two concentric spheres lit from the upper left, shared by the
+    /// world and camera tests.
+    pub fn test_default() -> Self {
+        use crate::{color::WHITE, material::Material, sphere::Sphere, transform::scaling};
+
+        let outer = Sphere::new().with_material(Material {
+            color: Color::new(0.8, 1.0, 0.6),
+            diffuse: 0.7,
+            specular: 0.2,
+            ..Material::default()
+        });
+        let inner = Sphere::new().with_transform(scaling(0.5, 0.5, 0.5));
+        World {
+            objects: vec![Box::new(outer), Box::new(inner)],
+            lights: vec![PointLight::new(Point::new(-10.0, 10.0, -10.0), WHITE)],
+            ..World::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_1_SQRT_2;
+
+    use float_cmp::approx_eq;
+
+    use crate::{
+        color::{Color, BLACK, WHITE},
+        intersection::{Intersection, Intersections},
+        light::PointLight,
+        material::Material,
+        pattern::tests::TestPattern,
+        plane::Plane,
+        point::Point,
+        ray::Ray,
+        shape::Shape,
+        sphere::Sphere,
+        transform::translation,
+        vector::Vector,
+        world::World,
+    };
+
+    #[test]
+    fn new_world_is_empty() {
+        let world = World::new();
+        assert!(world.objects.is_empty());
+        assert!(world.lights.is_empty());
+        assert_eq!(world.max_depth, 5);
+    }
+    #[test]
+    fn intersect_world_with_ray() {
+        let world = World::test_default();
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let intersections = world.intersect_world(&ray);
+        assert_eq!(intersections.len(), 4);
+        assert_eq!(intersections[0].t, 4.0);
+        assert_eq!(intersections[1].t, 4.5);
+        assert_eq!(intersections[2].t, 5.5);
+        assert_eq!(intersections[3].t, 6.0);
+    }
+    #[test]
+    fn shade_intersection() {
+        let world = World::test_default();
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let intersections =
+            Intersections::new(vec![Intersection::new(4.0, world.objects[0].as_ref())]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        let color = world.shade_hit(&comps, world.max_depth);
+        assert!(approx_eq!(
+            Color,
+            color,
+            Color::new(0.38066, 0.47583, 0.2855),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn shade_intersection_from_inside() {
+        let mut world = World::test_default();
+        world.lights = vec![PointLight::new(Point::new(0.0, 0.25, 0.0), WHITE)];
+        let ray = Ray::new(Point::origin(), Vector::z_unit());
+        let intersections =
+            Intersections::new(vec![Intersection::new(0.5, world.objects[1].as_ref())]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        let color = world.shade_hit(&comps, world.max_depth);
+        assert!(approx_eq!(
+            Color,
+            color,
+            Color::new(0.90498, 0.90498, 0.90498),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn shade_hit_sums_every_light() {
+        let mut world = World::test_default();
+        let light = world.lights[0];
+        world.lights.push(light);
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let intersections =
+            Intersections::new(vec![Intersection::new(4.0, world.objects[0].as_ref())]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        let color = world.shade_hit(&comps, world.max_depth);
+        assert!(approx_eq!(
+            Color,
+            color,
+            Color::new(0.38066, 0.47583, 0.2855) * 2.0,
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn color_when_ray_misses() {
+        let world = World::test_default();
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::y_unit());
+        assert_eq!(world.color_at(&ray), BLACK);
+    }
+    #[test]
+    fn color_when_ray_hits() {
+        let world = World::test_default();
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        assert!(approx_eq!(
+            Color,
+            world.color_at(&ray),
+            Color::new(0.38066, 0.47583, 0.2855),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn color_with_intersection_behind_ray() {
+        let mut world = World::test_default();
+        world.objects[0].material_mut().ambient = 1.0;
+        world.objects[1].material_mut().ambient = 1.0;
+        let ray = Ray::new(Point::new(0.0, 0.0, 0.75), Vector::new(0.0, 0.0, -1.0));
+        assert_eq!(world.color_at(&ray), world.objects[1].material().color);
+    }
+    #[test]
+    fn no_shadow_when_nothing_collinear_with_point_and_light() {
+        let world = World::test_default();
+        let point = Point::new(0.0, 10.0, 0.0);
+        assert!(!world.is_shadowed(&world.lights[0], point));
+    }
+    #[test]
+    fn shadow_when_object_between_point_and_light() {
+        let world = World::test_default();
+        let point = Point::new(10.0, -10.0, 10.0);
+        assert!(world.is_shadowed(&world.lights[0], point));
+    }
+    #[test]
+    fn no_shadow_when_object_behind_light() {
+        let world = World::test_default();
+        let point = Point::new(-20.0, 20.0, -20.0);
+        assert!(!world.is_shadowed(&world.lights[0], point));
+    }
+    #[test]
+    fn no_shadow_when_object_behind_point() {
+        let world = World::test_default();
+        let point = Point::new(-2.0, 2.0, -2.0);
+        assert!(!world.is_shadowed(&world.lights[0], point));
+    }
+    #[test]
+    fn shade_hit_in_shadow() {
+        let world = World {
+            objects: vec![
+                Box::new(Sphere::new()),
+                Box::new(Sphere::new().with_transform(translation(0.0, 0.0, 10.0))),
+            ],
+            lights: vec![PointLight::new(Point::new(0.0, 0.0, -10.0), WHITE)],
+            ..World::new()
+        };
+        let ray = Ray::new(Point::new(0.0, 0.0, 5.0), Vector::z_unit());
+        let intersections =
+            Intersections::new(vec![Intersection::new(4.0, world.objects[1].as_ref())]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        let color = world.shade_hit(&comps, world.max_depth);
+        assert_eq!(color, Color::new(0.1, 0.1, 0.1));
+    }
+    #[test]
+    fn reflected_color_for_nonreflective_material() {
+        let mut world = World::test_default();
+        world.objects[1].material_mut().ambient = 1.0;
+        let ray = Ray::new(Point::origin(), Vector::z_unit());
+        let intersections =
+            Intersections::new(vec![Intersection::new(1.0, world.objects[1].as_ref())]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_eq!(world.reflected_color(&comps, world.max_depth), BLACK);
+    }
+    #[test]
+    fn reflected_color_for_reflective_material() {
+        let mut world = World::test_default();
+        world.objects.push(Box::new(
+            Plane::new()
+                .with_material(Material {
+                    reflective: 0.5,
+                    ..Material::default()
+                })
+                .with_transform(translation(0.0, -1.0, 0.0)),
+        ));
+        let ray = Ray::new(
+            Point::new(0.0, 0.0, -3.0),
+            Vector::new(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let intersections = Intersections::new(vec![Intersection::new(
+            2.0_f32.sqrt(),
+            world.objects[2].as_ref(),
+        )]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(approx_eq!(
+            Color,
+            world.reflected_color(&comps, world.max_depth),
+            Color::new(0.19032, 0.2379, 0.14274),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn shade_hit_with_reflective_material() {
+        let mut world = World::test_default();
+        world.objects.push(Box::new(
+            Plane::new()
+                .with_material(Material {
+                    reflective: 0.5,
+                    ..Material::default()
+                })
+                .with_transform(translation(0.0, -1.0, 0.0)),
+        ));
+        let ray = Ray::new(
+            Point::new(0.0, 0.0, -3.0),
+            Vector::new(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let intersections = Intersections::new(vec![Intersection::new(
+            2.0_f32.sqrt(),
+            world.objects[2].as_ref(),
+        )]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(approx_eq!(
+            Color,
+            world.shade_hit(&comps, world.max_depth),
+            Color::new(0.87677, 0.92436, 0.82918),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn mutually_reflective_surfaces_terminate() {
+        let mirror = Material {
+            reflective: 1.0,
+            ..Material::default()
+        };
+        let world = World {
+            objects: vec![
+                Box::new(
+                    Plane::new()
+                        .with_material(mirror.clone())
+                        .with_transform(translation(0.0, -1.0, 0.0)),
+                ),
+                Box::new(
+                    Plane::new()
+                        .with_material(mirror)
+                        .with_transform(translation(0.0, 1.0, 0.0)),
+                ),
+            ],
+            lights: vec![PointLight::new(Point::origin(), WHITE)],
+            ..World::new()
+        };
+        let ray = Ray::new(Point::origin(), Vector::y_unit());
+        // Bounded by max_depth rather than overflowing the stack: the initial
+        // hit plus max_depth reflections, each lit with ambient + diffuse +
+        // specular = 1.9 from the light between the planes
+        let bounces = (world.max_depth + 1) as f32;
+        assert!(approx_eq!(
+            Color,
+            world.color_at(&ray),
+            WHITE * (1.9 * bounces),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn reflected_color_at_maximum_depth() {
+        let mut world = World::test_default();
+        world.objects.push(Box::new(
+            Plane::new()
+                .with_material(Material {
+                    reflective: 0.5,
+                    ..Material::default()
+                })
+                .with_transform(translation(0.0, -1.0, 0.0)),
+        ));
+        let ray = Ray::new(
+            Point::new(0.0, 0.0, -3.0),
+            Vector::new(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let intersections = Intersections::new(vec![Intersection::new(
+            2.0_f32.sqrt(),
+            world.objects[2].as_ref(),
+        )]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_eq!(world.reflected_color(&comps, 0), BLACK);
+    }
+    #[test]
+    fn max_depth_is_configurable() {
+        let mut world = World::test_default();
+        world.objects.push(Box::new(
+            Plane::new()
+                .with_material(Material {
+                    reflective: 0.5,
+                    ..Material::default()
+                })
+                .with_transform(translation(0.0, -1.0, 0.0)),
+        ));
+        let ray = Ray::new(
+            Point::new(0.0, 0.0, -3.0),
+            Vector::new(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let reflected = world.color_at(&ray);
+        world.max_depth = 0;
+        let unreflected = world.color_at(&ray);
+        assert!(approx_eq!(
+            Color,
+            reflected - unreflected,
+            Color::new(0.19032, 0.2379, 0.14274),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn refracted_color_with_opaque_surface() {
+        let world = World::test_default();
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let intersections = Intersections::new(vec![
+            Intersection::new(4.0, world.objects[0].as_ref()),
+            Intersection::new(6.0, world.objects[0].as_ref()),
+        ]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_eq!(world.refracted_color(&comps, 5), BLACK);
+    }
+    #[test]
+    fn refracted_color_at_maximum_depth() {
+        let mut world = World::test_default();
+        let material = world.objects[0].material_mut();
+        material.transparency = 1.0;
+        material.refractive_index = 1.5;
+        let ray = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::z_unit());
+        let intersections = Intersections::new(vec![
+            Intersection::new(4.0, world.objects[0].as_ref()),
+            Intersection::new(6.0, world.objects[0].as_ref()),
+        ]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert_eq!(world.refracted_color(&comps, 0), BLACK);
+    }
+    #[test]
+    fn refracted_color_under_total_internal_reflection() {
+        let mut world = World::test_default();
+        let material = world.objects[0].material_mut();
+        material.transparency = 1.0;
+        material.refractive_index = 1.5;
+        let ray = Ray::new(Point::new(0.0, 0.0, FRAC_1_SQRT_2), Vector::y_unit());
+        let intersections = Intersections::new(vec![
+            Intersection::new(-FRAC_1_SQRT_2, world.objects[0].as_ref()),
+            Intersection::new(FRAC_1_SQRT_2, world.objects[0].as_ref()),
+        ]);
+        // Inside the sphere, so the second intersection is the one that matters
+        let comps = intersections[1].prepare_computations(&ray, &intersections);
+        assert_eq!(world.refracted_color(&comps, 5), BLACK);
+    }
+    #[test]
+    fn refracted_color_with_refracted_ray() {
+        let mut world = World::test_default();
+        let outer = world.objects[0].material_mut();
+        outer.ambient = 1.0;
+        *outer = outer.clone().with_pattern(TestPattern::new());
+        let inner = world.objects[1].material_mut();
+        inner.transparency = 1.0;
+        inner.refractive_index = 1.5;
+        let ray = Ray::new(Point::new(0.0, 0.0, 0.1), Vector::y_unit());
+        let intersections = Intersections::new(vec![
+            Intersection::new(-0.9899, world.objects[0].as_ref()),
+            Intersection::new(-0.4899, world.objects[1].as_ref()),
+            Intersection::new(0.4899, world.objects[1].as_ref()),
+            Intersection::new(0.9899, world.objects[0].as_ref()),
+        ]);
+        let comps = intersections[2].prepare_computations(&ray, &intersections);
+        assert!(approx_eq!(
+            Color,
+            world.refracted_color(&comps, 5),
+            Color::new(0.0, 0.99888, 0.04725),
+            epsilon = 0.001
+        ));
+    }
+    #[test]
+    fn shade_hit_with_transparent_material() {
+        let mut world = World::test_default();
+        world.objects.push(Box::new(
+            Plane::new()
+                .with_material(Material {
+                    transparency: 0.5,
+                    refractive_index: 1.5,
+                    ..Material::default()
+                })
+                .with_transform(translation(0.0, -1.0, 0.0)),
+        ));
+        world.objects.push(Box::new(
+            Sphere::new()
+                .with_material(Material {
+                    color: Color::new(1.0, 0.0, 0.0),
+                    ambient: 0.5,
+                    ..Material::default()
+                })
+                .with_transform(translation(0.0, -3.5, -0.5)),
+        ));
+        let ray = Ray::new(
+            Point::new(0.0, 0.0, -3.0),
+            Vector::new(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let intersections = Intersections::new(vec![Intersection::new(
+            2.0_f32.sqrt(),
+            world.objects[2].as_ref(),
+        )]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(approx_eq!(
+            Color,
+            world.shade_hit(&comps, 5),
+            Color::new(0.93642, 0.68642, 0.68642),
+            epsilon = 0.0001
+        ));
+    }
+    #[test]
+    fn shade_hit_with_reflective_transparent_material() {
+        let mut world = World::test_default();
+        world.objects.push(Box::new(
+            Plane::new()
+                .with_material(Material {
+                    reflective: 0.5,
+                    transparency: 0.5,
+                    refractive_index: 1.5,
+                    ..Material::default()
+                })
+                .with_transform(translation(0.0, -1.0, 0.0)),
+        ));
+        world.objects.push(Box::new(
+            Sphere::new()
+                .with_material(Material {
+                    color: Color::new(1.0, 0.0, 0.0),
+                    ambient: 0.5,
+                    ..Material::default()
+                })
+                .with_transform(translation(0.0, -3.5, -0.5)),
+        ));
+        let ray = Ray::new(
+            Point::new(0.0, 0.0, -3.0),
+            Vector::new(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let intersections = Intersections::new(vec![Intersection::new(
+            2.0_f32.sqrt(),
+            world.objects[2].as_ref(),
+        )]);
+        let comps = intersections[0].prepare_computations(&ray, &intersections);
+        assert!(approx_eq!(
+            Color,
+            world.shade_hit(&comps, 5),
+            Color::new(0.93391, 0.69643, 0.69243),
+            epsilon = 0.0001
+        ));
+    }
+}